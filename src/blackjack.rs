@@ -3,5 +3,10 @@ pub mod blackjack_hand;
 pub mod game;
 pub mod hand_value;
 pub mod player;
+pub mod settlement;
+pub mod side_bet;
+pub mod spanish_21;
+pub mod table;
+pub mod table_rules;
 pub mod ui;
 pub mod wager;
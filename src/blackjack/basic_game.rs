@@ -1,44 +1,80 @@
 use core::borrow::Borrow;
-
-use failure::{format_err, Error, Fail};
-
-use crate::blackjack::player::Player;
+use std::sync::Arc;
+
+use crate::blackjack::player::{ActionError, Player};
+use crate::blackjack::settlement::Settlement;
+use crate::blackjack::side_bet::SideBet;
+use crate::blackjack::table::{DealingError, Table};
+use crate::blackjack::table_rules::TableRules;
+use crate::cards::card::Card;
 use crate::cards::shoe::Shoe;
-use crate::cards::shuffleable::Shuffleable;
-
-#[derive(Debug, Fail)]
-pub enum DealingError {
-    #[fail(display = "Failed to take card from shoe")]
-    TakingCardFromShoe,
-    #[fail(display = "Failed to push card to player's current hand")]
-    PushCardToPlayer,
-}
+use uuid::Uuid;
 
 pub struct BasicGame {
     players: Vec<Player>,
-    dealer: Player,
-    shoe: Shoe,
+    table: Table,
 }
 
 impl BasicGame {
     pub fn new() -> BasicGame {
-        BasicGame {
-            players: Vec::with_capacity(7),
-            dealer: Player::new_dealer(String::from("Dealer")),
-            shoe: Shoe::with_decks(6),
-        }
+        BasicGame::with_rules(TableRules::default())
     }
 
     pub fn with_n_decks(n: u32) -> BasicGame {
+        BasicGame::with_rules(TableRules {
+            decks: n,
+            ..Default::default()
+        })
+    }
+
+    pub fn with_shoe(shoe: Shoe) -> BasicGame {
+        BasicGame::with_rules_and_shoe(TableRules::default(), shoe)
+    }
+
+    pub fn with_rules(rules: TableRules) -> BasicGame {
+        let shoe = Shoe::with_deck_spec(rules.decks, rules.deck.clone());
+        BasicGame::with_rules_and_shoe(rules, shoe)
+    }
+
+    pub fn with_rules_and_shoe(rules: TableRules, shoe: Shoe) -> BasicGame {
         BasicGame {
-            players: Vec::with_capacity(7),
-            dealer: Player::new_dealer(String::from("Dealer")),
-            shoe: Shoe::with_decks(n),
+            players: Vec::with_capacity(rules.seats),
+            table: Table::new(rules, shoe),
         }
     }
 
+    pub fn get_rules(&self) -> &TableRules {
+        self.table.get_rules()
+    }
+
+    pub fn get_shoe(&self) -> &Shoe {
+        self.table.get_shoe()
+    }
+
     pub fn setup(&mut self) {
-        self.shoe.shuffle();
+        self.table.shoe_mut().reshuffle();
+    }
+
+    // returns true when the card burned for the new dealer brought out the cut
+    // card and the shoe was reshuffled
+    pub fn change_dealer(&mut self) -> bool {
+        self.table.change_dealer()
+    }
+
+    // returns true when the shoe was reshuffled because the cut card came out
+    pub fn start_round(&mut self) -> bool {
+        self.table.start_round()
+    }
+
+    // collects the cards from the finished round and clears every hand
+    pub fn end_round(&mut self) {
+        self.table.end_round(&mut self.players);
+    }
+
+    // returns true when the dealer has blackjack, once checked the players
+    // can no longer early surrender and late surrender becomes available
+    pub fn check_dealer_blackjack(&mut self) -> bool {
+        self.table.check_dealer_blackjack()
     }
 
     pub fn add_player(&mut self, player: Player) {
@@ -46,7 +82,7 @@ impl BasicGame {
     }
 
     pub fn can_add_player(&self) -> bool {
-        self.players.len() < self.players.capacity()
+        self.players.len() < self.get_rules().seats
     }
 
     pub fn get_players(&self) -> &Vec<Player> {
@@ -54,65 +90,164 @@ impl BasicGame {
     }
 
     pub fn get_dealer(&self) -> &Player {
-        &self.dealer
+        self.table.get_dealer()
     }
 
-    pub fn deal_cards(&mut self) -> Result<(), DealingError> {
-        for _ in 0..2 {
-            match (
-                self.deal_single_card_to_all_players(),
-                self.deal_card_to_dealer(),
-            ) {
-                (Err(e), _) | (_, Err(e)) => {
-                    return Err(e);
-                }
-                _ => {}
-            }
-        }
-        return Ok(());
+    pub fn hit(&mut self, player_id: Uuid) -> Result<Arc<Card>, ActionError> {
+        self.table.hit(find_player(&mut self.players, player_id)?)
+    }
+
+    pub fn stand(&mut self, player_id: Uuid) -> Result<(), ActionError> {
+        find_player(&mut self.players, player_id)?.stand()
     }
 
-    fn deal_single_card_to_all_players(&mut self) -> Result<(), DealingError> {
-        let shoe = &mut self.shoe;
-        let mut result: Result<(), DealingError> = Ok(());
+    pub fn twist(&mut self, player_id: Uuid) -> Result<Arc<Card>, ActionError> {
+        self.table.twist(find_player(&mut self.players, player_id)?)
+    }
 
-        self.players.iter_mut().for_each(|p| {
-            let card = shoe.take_card();
+    pub fn stick(&mut self, player_id: Uuid) -> Result<(), ActionError> {
+        self.table.stick(find_player(&mut self.players, player_id)?)
+    }
 
-            if card.is_none() {
-                result = Err(DealingError::TakingCardFromShoe);
-                return;
-            }
+    pub fn buy(
+        &mut self,
+        player_id: Uuid,
+        wager: u32,
+    ) -> Result<Arc<Card>, ActionError> {
+        let player = find_player(&mut self.players, player_id)?;
+        self.table.buy(player, wager)
+    }
 
-            let card = card.unwrap();
+    pub fn is_insurance_offered(&self) -> bool {
+        self.table.is_insurance_offered()
+    }
 
-            if p.push_card_to_current_hand(card.clone()).is_err() {
-                result = Err(DealingError::PushCardToPlayer);
-                return;
-            }
-        });
+    pub fn place_insurance(
+        &mut self,
+        player_id: Uuid,
+        wager: u32,
+    ) -> Result<(), ActionError> {
+        let player = find_player(&mut self.players, player_id)?;
+        self.table.place_insurance(player, wager)
+    }
 
-        result
+    pub fn take_even_money(
+        &mut self,
+        player_id: Uuid,
+    ) -> Result<u32, ActionError> {
+        let player = find_player(&mut self.players, player_id)?;
+        self.table.take_even_money(player)
     }
 
-    fn deal_card_to_dealer(&mut self) -> Result<(), DealingError> {
-        let shoe = &mut self.shoe;
-        let mut result: Result<(), DealingError> = Ok(());
+    // closes the insurance window by checking the hole card, returns true
+    // when the dealer has blackjack
+    pub fn settle_insurance(&mut self) -> bool {
+        self.table.settle_insurance(&mut self.players)
+    }
 
-        if let Some(card) = shoe.take_card() {
-            if self.dealer.push_card_to_current_hand(card.clone()).is_err() {
-                result = Err(DealingError::PushCardToPlayer);
-            }
-        } else {
-            result = Err(DealingError::TakingCardFromShoe);
-        }
+    // the dealer draws until the hand stands, the dealt cards are returned
+    pub fn play_dealer(&mut self) -> Result<Vec<Arc<Card>>, ActionError> {
+        self.table.play_dealer(&mut self.players)
+    }
+
+    // settles every player's hands against the dealer and pays out
+    pub fn settle_round(&mut self) -> Vec<Settlement> {
+        self.table.settle_round(&mut self.players)
+    }
+
+    pub fn place_switch_wagers(
+        &mut self,
+        player_id: Uuid,
+        wager: u32,
+    ) -> Result<(), ActionError> {
+        let player = find_player(&mut self.players, player_id)?;
+        self.table.place_switch_wagers(player, wager)
+    }
+
+    pub fn place_side_bet(
+        &mut self,
+        player_id: Uuid,
+        bet: Box<dyn SideBet>,
+        wager: u32,
+    ) -> Result<(), ActionError> {
+        find_player(&mut self.players, player_id)?.place_side_bet(bet, wager)
+    }
+
+    pub fn switch(&mut self, player_id: Uuid) -> Result<(), ActionError> {
+        find_player(&mut self.players, player_id)?.switch()
+    }
+
+    // returns the double down wager that was refunded to the player
+    pub fn rescue(&mut self, player_id: Uuid) -> Result<u32, ActionError> {
+        self.table.rescue(find_player(&mut self.players, player_id)?)
+    }
+
+    // returns the half of the wager that was refunded to the player
+    pub fn surrender(&mut self, player_id: Uuid) -> Result<u32, ActionError> {
+        self.table.surrender(find_player(&mut self.players, player_id)?)
+    }
 
-        result
+    pub fn split(
+        &mut self,
+        player_id: Uuid,
+    ) -> Result<Vec<Arc<Card>>, ActionError> {
+        self.table.split(find_player(&mut self.players, player_id)?)
     }
+
+    pub fn double_down(
+        &mut self,
+        player_id: Uuid,
+    ) -> Result<Arc<Card>, ActionError> {
+        self.table.double_down(find_player(&mut self.players, player_id)?)
+    }
+
+    pub fn double_for_less(
+        &mut self,
+        player_id: Uuid,
+        wager: u32,
+    ) -> Result<Arc<Card>, ActionError> {
+        let player = find_player(&mut self.players, player_id)?;
+        self.table.double_for_less(player, wager)
+    }
+
+    pub fn deal_cards(&mut self) -> Result<(), DealingError> {
+        self.table.deal_cards(&mut self.players).map(|_| ())
+    }
+
+    // returns true when the dealer has blackjack and the round is over
+    pub fn peek(&mut self) -> bool {
+        self.table.peek(&mut self.players)
+    }
+
+    // deals the dealer's second card when playing without a hole card, returns
+    // true when it gives the dealer blackjack
+    pub fn deal_dealer_second_card(&mut self) -> Result<bool, DealingError> {
+        let dealt = self.table.deal_dealer_second_card(&mut self.players)?;
+        Ok(dealt.is_some() && self.table.is_dealer_blackjack())
+    }
+}
+
+fn find_player(
+    players: &mut Vec<Player>,
+    player_id: Uuid,
+) -> Result<&mut Player, ActionError> {
+    players
+        .iter_mut()
+        .find(|p| p.get_id() == player_id)
+        .ok_or(ActionError::PlayerNotFound)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
+    use crate::blackjack::blackjack_hand::ResultsState;
+    use crate::blackjack::hand_value::{HandValue, WithHandValue};
+    use crate::blackjack::side_bet::twenty_one_plus_three::TwentyOnePlusThree;
+    use crate::blackjack::table_rules::{HoleCard, Surrender};
+    use crate::blackjack::wager::WithWager;
+    use crate::cards::rank::Rank;
+
     use super::*;
 
     #[test]
@@ -156,6 +291,554 @@ mod tests {
         assert_eq!(current_hand.get_card_count(), 2);
     }
 
+    #[test]
+    fn it_replays_game_from_shoe_seed() {
+        let mut game = BasicGame::with_shoe(Shoe::with_decks_and_seed(6, 9));
+        let mut replay = BasicGame::with_shoe(Shoe::with_decks_and_seed(
+            6,
+            game.get_shoe().get_seed(),
+        ));
+
+        game.setup();
+        replay.setup();
+
+        assert!(game.deal_cards().is_ok());
+        assert!(replay.deal_cards().is_ok());
+
+        assert_eq!(
+            game.get_dealer().get_current_hand().unwrap().get_cards(),
+            replay.get_dealer().get_current_hand().unwrap().get_cards()
+        );
+    }
+
+    #[test]
+    fn it_discards_cards_at_end_of_round() {
+        let mut game = BasicGame::new();
+
+        game.add_player(Player::new(String::from("Tester")));
+        game.start_round();
+        assert!(game.deal_cards().is_ok());
+
+        game.end_round();
+
+        assert_eq!(game.get_shoe().get_discarded_card_count(), 4);
+        assert_eq!(game.get_shoe().get_remaining_card_count(), 52 * 6 - 4);
+
+        let dealer = game.get_dealer();
+        assert_eq!(dealer.get_current_hand().unwrap().get_card_count(), 0);
+    }
+
+    #[test]
+    fn it_never_reshuffles_continuous_shuffler() {
+        let mut game = BasicGame::with_shoe(Shoe::continuous_with_decks(1));
+
+        game.add_player(Player::new(String::from("Tester")));
+        game.setup();
+
+        for _ in 0..50 {
+            assert!(!game.start_round());
+            assert!(game.deal_cards().is_ok());
+            game.end_round();
+
+            assert_eq!(game.get_shoe().get_remaining_card_count(), 52);
+        }
+    }
+
+    #[test]
+    fn it_reshuffles_when_dealer_change_reaches_cut_card() {
+        let mut shoe = Shoe::with_decks(1);
+        shoe.set_penetration(0.0);
+        shoe.set_burn_on_dealer_change(true);
+
+        let mut game = BasicGame::with_shoe(shoe);
+        game.setup();
+
+        assert!(game.change_dealer());
+        assert_eq!(game.get_shoe().get_remaining_card_count(), 52);
+        assert_eq!(game.get_shoe().get_discarded_card_count(), 0);
+    }
+
+    #[test]
+    fn it_never_deals_burned_cards() {
+        let mut shoe = Shoe::with_decks(1);
+        shoe.set_burn_on_shuffle(1);
+        shoe.set_burn_on_dealer_change(true);
+
+        let mut game = BasicGame::with_shoe(shoe);
+        game.add_player(Player::new(String::from("Tester")));
+        game.setup();
+
+        assert!(!game.change_dealer());
+
+        let burned = game.get_shoe().get_discard_tray().get_cards().clone();
+        assert_eq!(burned.len(), 2);
+
+        game.start_round();
+        assert!(game.deal_cards().is_ok());
+
+        let player = game.get_players().first().unwrap();
+        let dealt = player.get_current_hand().unwrap().get_cards();
+
+        for card in dealt.iter() {
+            assert!(!burned.iter().any(|b| Arc::ptr_eq(b, card)));
+        }
+
+        game.end_round();
+        assert_eq!(game.get_shoe().get_discarded_card_count(), 6);
+    }
+
+    #[test]
+    fn it_deals_natural_from_stacked_shoe() {
+        let shoe = Shoe::from_str("A\u{2660} 9\u{2665} K\u{2660} 7\u{2666}");
+        let mut game = BasicGame::with_shoe(shoe.unwrap());
+
+        game.add_player(Player::new(String::from("Tester")));
+        game.setup();
+        assert!(game.deal_cards().is_ok());
+
+        let player = game.get_players().first().unwrap();
+        let hand = player.get_current_hand().unwrap();
+        assert_eq!(hand.get_value(), Some(HandValue::Ace(11, 21)));
+
+        let dealer = game.get_dealer().get_current_hand().unwrap();
+        assert_eq!(dealer.get_value(), Some(HandValue::V(16)));
+        assert!(dealer.can_hit(game.get_rules()));
+    }
+
+    #[test]
+    fn it_fails_to_deal_when_stacked_shoe_runs_out() {
+        let shoe = Shoe::from_str("A\u{2660} 9\u{2665} K\u{2660}");
+        let mut game = BasicGame::with_shoe(shoe.unwrap());
+
+        game.add_player(Player::new(String::from("Tester")));
+
+        match game.deal_cards() {
+            Err(DealingError::TakingCardFromShoe) => {}
+            _ => panic!("Expected the shoe to run out of cards"),
+        }
+    }
+
+    #[test]
+    fn it_uses_table_rules() {
+        let mut game = BasicGame::with_rules(TableRules {
+            decks: 2,
+            seats: 1,
+            ..Default::default()
+        });
+
+        assert_eq!(game.get_shoe().get_remaining_card_count(), 104);
+
+        game.add_player(Player::new(String::from("Tester")));
+
+        assert!(!game.can_add_player());
+    }
+
+    #[test]
+    fn it_splits_players_hand() {
+        let shoe = Shoe::from_str("8S 9H 8H 7D 3C 2D KS");
+        let mut game = BasicGame::with_shoe(shoe.unwrap());
+
+        let mut player = Player::new(String::from("Tester"));
+        let id = player.get_id();
+        player.add_funds(100);
+        player.place_wager(10).unwrap();
+
+        game.add_player(player);
+        assert!(game.deal_cards().is_ok());
+        assert!(game.split(id).is_ok());
+
+        assert_eq!(game.hit(id).unwrap().to_string(), "\u{2660}K");
+        assert!(game.stand(id).is_ok());
+        assert!(game.stand(id).is_ok());
+
+        let player = game.get_players().first().unwrap();
+        let hands = player.get_hands();
+
+        assert!(player.is_done());
+        assert_eq!(hands[0].get_value(), Some(HandValue::V(21)));
+        assert_eq!(hands[1].get_value(), Some(HandValue::V(10)));
+        assert_eq!(player.get_available_funds(), 80);
+
+        assert_eq!(
+            game.split(Uuid::new_v4()),
+            Err(ActionError::PlayerNotFound)
+        );
+    }
+
+    #[test]
+    fn it_doubles_down_players_hand() {
+        let shoe = Shoe::from_str("5S 9H 6H 7D KS");
+        let mut game = BasicGame::with_shoe(shoe.unwrap());
+
+        let mut player = Player::new(String::from("Tester"));
+        let id = player.get_id();
+        player.add_funds(100);
+        player.place_wager(10).unwrap();
+
+        game.add_player(player);
+        assert!(game.deal_cards().is_ok());
+        assert!(game.double_down(id).is_ok());
+
+        let player = game.get_players().first().unwrap();
+        let hand = player.get_hands().first().unwrap();
+
+        assert!(player.is_done());
+        assert_eq!(hand.get_value(), Some(HandValue::V(21)));
+        assert_eq!(hand.get_wagered_value(), 20);
+        assert_eq!(player.get_available_funds(), 80);
+    }
+
+    #[test]
+    fn it_late_surrenders_after_dealer_checks() {
+        let shoe = Shoe::from_str("10S 9H 6H 7D");
+        let rules = TableRules {
+            surrender: Surrender::Late,
+            ..Default::default()
+        };
+        let mut game = BasicGame::with_rules_and_shoe(rules, shoe.unwrap());
+
+        let mut player = Player::new(String::from("Tester"));
+        let id = player.get_id();
+        player.add_funds(100);
+        player.place_wager(10).unwrap();
+
+        game.add_player(player);
+        assert!(game.deal_cards().is_ok());
+
+        assert_eq!(game.surrender(id), Err(ActionError::NotAllowed));
+        assert!(!game.check_dealer_blackjack());
+        assert_eq!(game.surrender(id), Ok(5));
+
+        let player = game.get_players().first().unwrap();
+        assert!(player.is_done());
+        assert_eq!(player.get_available_funds(), 95);
+    }
+
+    #[test]
+    fn it_settles_insurance_against_dealer_blackjack() {
+        let shoe = Shoe::from_str("10S AH 6H KD");
+        let mut game = BasicGame::with_shoe(shoe.unwrap());
+
+        let mut player = Player::new(String::from("Tester"));
+        let id = player.get_id();
+        player.add_funds(100);
+        player.place_wager(10).unwrap();
+
+        game.add_player(player);
+        assert!(game.place_insurance(id, 5).is_err());
+        assert!(game.deal_cards().is_ok());
+
+        assert!(game.is_insurance_offered());
+        assert!(game.place_insurance(id, 5).is_ok());
+        assert!(game.settle_insurance());
+        assert!(game.place_insurance(id, 5).is_err());
+
+        let player = game.get_players().first().unwrap();
+        assert_eq!(player.get_available_funds(), 100);
+    }
+
+    #[test]
+    fn it_ends_round_when_dealer_peeks_blackjack() {
+        let shoe = Shoe::from_str("10S KH 6H AD");
+        let mut game = BasicGame::with_shoe(shoe.unwrap());
+
+        let player = Player::new(String::from("Tester"));
+        let id = player.get_id();
+
+        game.add_player(player);
+        assert!(game.deal_cards().is_ok());
+        assert!(game.peek());
+
+        assert!(game.get_players().first().unwrap().is_done());
+        assert_eq!(game.hit(id), Err(ActionError::NoCurrentHand));
+    }
+
+    #[test]
+    fn it_does_not_peek_under_low_upcard() {
+        let shoe = Shoe::from_str("10S 6H 6H AD");
+        let mut game = BasicGame::with_shoe(shoe.unwrap());
+
+        game.add_player(Player::new(String::from("Tester")));
+        assert!(game.deal_cards().is_ok());
+        assert!(!game.peek());
+        assert!(!game.get_players().first().unwrap().is_done());
+    }
+
+    #[test]
+    fn it_deals_no_hole_card() {
+        let shoe = Shoe::from_str("8S AH 8H 3C 2D KS");
+        let rules = TableRules {
+            hole_card: HoleCard::OriginalBetsOnly,
+            ..Default::default()
+        };
+        let mut game = BasicGame::with_rules_and_shoe(rules, shoe.unwrap());
+
+        let mut player = Player::new(String::from("Tester"));
+        let id = player.get_id();
+        player.add_funds(100);
+        player.place_wager(10).unwrap();
+
+        game.add_player(player);
+        assert!(game.deal_cards().is_ok());
+        assert_eq!(
+            game.get_dealer().get_current_hand().unwrap().get_card_count(),
+            1
+        );
+        assert!(!game.peek());
+        assert!(!game.is_insurance_offered());
+
+        assert!(game.split(id).is_ok());
+        assert!(game.stand(id).is_ok());
+        assert!(game.stand(id).is_ok());
+        assert_eq!(game.deal_dealer_second_card().ok(), Some(true));
+
+        let player = game.get_players().first().unwrap();
+        assert_eq!(player.get_available_funds(), 90);
+    }
+
+    #[test]
+    fn it_deals_second_card_when_playing_dealer() {
+        let shoe = Shoe::from_str("8S AH 8H 3C 2D KS");
+        let rules = TableRules {
+            hole_card: HoleCard::OriginalBetsOnly,
+            ..Default::default()
+        };
+        let mut game = BasicGame::with_rules_and_shoe(rules, shoe.unwrap());
+
+        let mut player = Player::new(String::from("Tester"));
+        let id = player.get_id();
+        player.add_funds(100);
+        player.place_wager(10).unwrap();
+
+        game.add_player(player);
+        assert!(game.deal_cards().is_ok());
+        assert!(game.split(id).is_ok());
+        assert!(game.stand(id).is_ok());
+        assert!(game.stand(id).is_ok());
+
+        // the dealer's blackjack still refunds the split under obo
+        assert_eq!(game.play_dealer().unwrap().len(), 1);
+        assert!(game.get_dealer().get_current_hand().unwrap().is_blackjack());
+        assert_eq!(game.get_players()[0].get_available_funds(), 90);
+        assert_eq!(game.deal_dealer_second_card().ok(), Some(false));
+    }
+
+    #[test]
+    fn it_settles_round() {
+        let shoe = Shoe::from_str("10S AH 10D 10C 9H KD 7C 6S 2C");
+        let mut game = BasicGame::with_shoe(shoe.unwrap());
+
+        let mut ids = vec![];
+        for _ in 0..3 {
+            let mut player = Player::new(String::from("Tester"));
+            ids.push(player.get_id());
+            player.add_funds(100);
+            player.place_wager(10).unwrap();
+            game.add_player(player);
+        }
+
+        assert!(game.deal_cards().is_ok());
+        assert!(!game.peek());
+
+        assert!(game.stand(ids[0]).is_ok());
+        assert!(game.stand(ids[1]).is_ok());
+        assert!(game.stand(ids[2]).is_ok());
+
+        // the dealer stands on 16 + 2 = 18
+        assert_eq!(game.play_dealer().unwrap().len(), 1);
+
+        let settlements = game.settle_round();
+        let results: Vec<_> = settlements
+            .iter()
+            .map(|s| (s.result.clone(), s.get_net()))
+            .collect();
+
+        assert_eq!(
+            results,
+            vec![
+                (ResultsState::Won, 10),
+                (ResultsState::Natural, 15),
+                (ResultsState::Lost, -10),
+            ]
+        );
+
+        let funds: Vec<_> = game
+            .get_players()
+            .iter()
+            .map(|p| p.get_available_funds())
+            .collect();
+        assert_eq!(funds, vec![110, 115, 90]);
+        assert!(game.settle_round().is_empty());
+    }
+
+    #[test]
+    fn it_deals_spanish_21() {
+        let mut game = BasicGame::with_rules(TableRules::spanish_21());
+
+        assert_eq!(game.get_shoe().get_remaining_card_count(), 48 * 6);
+        assert_eq!(game.get_shoe().get_remaining_rank_count(Rank::Ten), 0);
+
+        game.add_player(Player::new(String::from("Tester")));
+        assert!(game.deal_cards().is_ok());
+    }
+
+    #[test]
+    fn it_settles_side_bets() {
+        let shoe = Shoe::from_str("7S 10S 8S 9D");
+        let mut game = BasicGame::with_rules_and_shoe(
+            TableRules::default(),
+            shoe.unwrap(),
+        );
+
+        let mut player = Player::new(String::from("Tester"));
+        let id = player.get_id();
+        player.add_funds(100);
+        player.place_wager(10).unwrap();
+
+        game.add_player(player);
+        let bet = Box::new(TwentyOnePlusThree::new());
+        assert!(game.place_side_bet(id, bet, 5).is_ok());
+        assert!(game.deal_cards().is_ok());
+        assert!(!game.peek());
+        assert!(game.stand(id).is_ok());
+        assert!(game.play_dealer().is_ok());
+
+        // 7-8-10 of spades is a flush, the 17 loses to the dealer's 19
+        let settlements = game.settle_round();
+        assert_eq!(settlements[0].result, ResultsState::Lost);
+        assert_eq!(settlements[0].side_bet_paid, 30);
+        assert_eq!(settlements[0].get_net(), 15);
+    }
+
+    #[test]
+    fn it_plays_pontoon() {
+        let shoe = Shoe::from_str("9S 10H 5C 5D 3D 4S 6C");
+        let rules = TableRules::pontoon();
+        let mut game = BasicGame::with_rules_and_shoe(rules, shoe.unwrap());
+
+        let mut player = Player::new(String::from("Tester"));
+        let id = player.get_id();
+        player.add_funds(100);
+        player.place_wager(10).unwrap();
+
+        game.add_player(player);
+        assert!(game.deal_cards().is_ok());
+        assert!(!game.is_insurance_offered());
+
+        // both of the dealer's cards are hidden until the dealer plays
+        let dealer = game.get_dealer().get_current_hand().unwrap();
+        assert!(!dealer.is_face_up(0));
+        assert!(!dealer.is_face_up(1));
+
+        assert!(!game.peek());
+        assert_eq!(game.stick(id), Err(ActionError::NotAllowed));
+        assert!(game.buy(id, 10).is_ok());
+        assert!(game.twist(id).is_ok());
+        assert!(game.stick(id).is_ok());
+        assert!(game.play_dealer().is_ok());
+
+        // the dealer draws to 21 and wins the tie
+        let settlements = game.settle_round();
+        assert_eq!(settlements[0].result, ResultsState::Lost);
+        assert_eq!(settlements[0].get_net(), -20);
+    }
+
+    #[test]
+    fn it_plays_blackjack_switch() {
+        let shoe = Shoe::from_str("AS 5H 10C 5C KD 6D 8S 6S");
+        let rules = TableRules::blackjack_switch();
+        let mut game = BasicGame::with_rules_and_shoe(rules, shoe.unwrap());
+
+        let mut player = Player::new(String::from("Tester"));
+        let id = player.get_id();
+        player.add_funds(100);
+
+        game.add_player(player);
+        assert!(game.place_switch_wagers(id, 10).is_ok());
+        assert!(game.deal_cards().is_ok());
+        assert!(game.switch(id).is_ok());
+        assert!(!game.peek());
+
+        assert!(game.stand(id).is_ok());
+        assert!(game.hit(id).is_ok());
+        assert!(game.stand(id).is_ok());
+        assert!(game.play_dealer().is_ok());
+
+        // the switched 21 isn't a natural, so the dealer's 22 pushes both
+        let results: Vec<_> = game
+            .settle_round()
+            .into_iter()
+            .map(|s| (s.result, s.paid))
+            .collect();
+
+        assert_eq!(
+            results,
+            vec![(ResultsState::Pushed, 10), (ResultsState::Pushed, 10)]
+        );
+    }
+
+    #[test]
+    fn it_settles_free_split() {
+        let shoe = Shoe::from_str("8S 10D 8H 7C 3C 10S 10H");
+        let rules = TableRules::free_bet();
+        let mut game = BasicGame::with_rules_and_shoe(rules, shoe.unwrap());
+
+        let mut player = Player::new(String::from("Tester"));
+        let id = player.get_id();
+        player.add_funds(100);
+        player.place_wager(10).unwrap();
+
+        game.add_player(player);
+        assert!(game.deal_cards().is_ok());
+        assert!(!game.peek());
+
+        assert!(game.split(id).is_ok());
+        assert!(game.hit(id).is_ok());
+        assert!(game.stand(id).is_ok());
+        assert!(game.stand(id).is_ok());
+        assert!(game.play_dealer().unwrap().is_empty());
+
+        // the free hand is paid its winnings without the player staking it
+        let nets: Vec<_> =
+            game.settle_round().iter().map(|s| s.get_net()).collect();
+        assert_eq!(nets, vec![10, 10]);
+
+        let player = game.get_players().first().unwrap();
+        assert_eq!(player.get_available_funds(), 120);
+    }
+
+    #[test]
+    fn it_deals_hole_card_face_down() {
+        let shoe = Shoe::from_str("10S 9H 6H 7D 5C");
+        let mut game = BasicGame::with_shoe(shoe.unwrap());
+
+        game.add_player(Player::new(String::from("Tester")));
+        assert!(game.deal_cards().is_ok());
+
+        let hand = game.get_dealer().get_current_hand().unwrap();
+        assert!(hand.is_face_up(0));
+        assert!(!hand.is_face_up(1));
+
+        assert!(game.play_dealer().is_ok());
+
+        let hand = game.get_dealer().get_current_hand().unwrap();
+        assert_eq!(hand.get_face_up_cards().len(), 3);
+    }
+
+    #[test]
+    fn it_exposes_both_dealer_cards() {
+        let shoe = Shoe::from_str("10S AH 6H 7D");
+        let rules = TableRules::double_exposure();
+        let mut game = BasicGame::with_rules_and_shoe(rules, shoe.unwrap());
+
+        game.add_player(Player::new(String::from("Tester")));
+        assert!(game.deal_cards().is_ok());
+
+        let hand = game.get_dealer().get_current_hand().unwrap();
+        assert_eq!(hand.get_face_up_cards().len(), 2);
+        assert!(!game.is_insurance_offered());
+    }
+
     #[test]
     fn it_does_not_deal_with_no_decks() {
         let mut game = BasicGame::with_n_decks(0);
@@ -1,9 +1,12 @@
-use crate::blackjack::hand_value::{HandValue, WithHandValue};
+use crate::blackjack::hand_value::{CardValue, HandTotal, WithHandValue};
 use crate::blackjack::player::PlayerType;
+use crate::blackjack::side_bet::SideBetStake;
+use crate::blackjack::table_rules::{DoubleDown, Surrender, TableRules};
 use crate::blackjack::wager::{Wager, WithWager};
-use crate::cards::{Card, Hand};
+use crate::cards::{Card, Hand, Rank};
 use std::sync::Arc;
 
+#[derive(PartialEq, Clone, Debug)]
 pub enum HandState {
     Default,
     WagerPlaced,
@@ -11,6 +14,7 @@ pub enum HandState {
     HandPlayed,
 }
 
+#[derive(PartialEq, Clone, Debug)]
 pub enum ResultsState {
     Default,
     Natural,
@@ -18,16 +22,35 @@ pub enum ResultsState {
     Lost,
     Won,
     Busted,
+    Surrendered,
 }
 
 // @todo likely need to split up the states even more -- consider individual wager state + pay state??
 
 pub struct BlackjackHand {
     hand: Hand,
+    // the indexes of the cards that were dealt face down
+    face_down: Vec<usize>,
     player_type: PlayerType,
     state: HandState,
     result_state: ResultsState,
     wager: Wager,
+    from_split: bool,
+    natural_after_split: bool,
+    double_down_wager: u32,
+    free_double_down_wager: u32,
+    insurance: Wager,
+    even_money: bool,
+    switch_linked: bool,
+    switched: bool,
+    // the hand holds the extra stake of a split rather than an original bet
+    split_stake: bool,
+    side_bets: Vec<SideBetStake>,
+    bought_wager: u32,
+    twisted: bool,
+    // paid back to the bankroll when the hand was resolved during play, e.g.
+    // half of the wager on a surrender
+    early_payout: Option<u32>,
 }
 
 impl BlackjackHand {
@@ -35,9 +58,23 @@ impl BlackjackHand {
         BlackjackHand {
             player_type,
             hand: Hand::new(),
+            face_down: vec![],
             state: HandState::Default,
             result_state: ResultsState::Default,
             wager: Wager::new(),
+            from_split: false,
+            natural_after_split: false,
+            double_down_wager: 0,
+            free_double_down_wager: 0,
+            insurance: Wager::new(),
+            even_money: false,
+            switch_linked: false,
+            switched: false,
+            split_stake: false,
+            side_bets: vec![],
+            bought_wager: 0,
+            twisted: false,
+            early_payout: None,
         }
     }
 
@@ -48,9 +85,23 @@ impl BlackjackHand {
         BlackjackHand {
             player_type,
             hand: Hand::with_cards(cards),
+            face_down: vec![],
             state: HandState::Default,
             result_state: ResultsState::Default,
             wager: Wager::new(),
+            from_split: false,
+            natural_after_split: false,
+            double_down_wager: 0,
+            free_double_down_wager: 0,
+            insurance: Wager::new(),
+            even_money: false,
+            switch_linked: false,
+            switched: false,
+            split_stake: false,
+            side_bets: vec![],
+            bought_wager: 0,
+            twisted: false,
+            early_payout: None,
         }
     }
 
@@ -70,6 +121,39 @@ impl BlackjackHand {
         self.hand.add_cards(cards);
     }
 
+    // e.g. the dealer's hole card
+    pub fn add_card_face_down(&mut self, card: Arc<Card>) {
+        self.face_down.push(self.hand.get_card_count());
+        self.hand.add_card(card);
+    }
+
+    pub fn is_face_up(&self, index: usize) -> bool {
+        index < self.hand.get_card_count() && !self.face_down.contains(&index)
+    }
+
+    pub fn get_face_up_cards(&self) -> Vec<&Arc<Card>> {
+        self.hand
+            .get_cards()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.face_down.contains(index))
+            .map(|(_, card)| card)
+            .collect()
+    }
+
+    // turns every card face up, returns the cards that were face down
+    pub fn reveal_cards(&mut self) -> Vec<Arc<Card>> {
+        let cards = self.hand.get_cards();
+        let revealed = self
+            .face_down
+            .iter()
+            .filter_map(|&index| cards.get(index).cloned())
+            .collect();
+
+        self.face_down.clear();
+        revealed
+    }
+
     pub fn get_cards(&self) -> &Vec<Arc<Card>> {
         self.hand.get_cards()
     }
@@ -78,23 +162,152 @@ impl BlackjackHand {
         self.hand.get_card_count()
     }
 
-    pub fn can_split(&self) -> bool {
-        match self.player_type {
-            PlayerType::Dealer => false,
-            PlayerType::Player => {
-                self.hand.get_card_count() == 2
-                    && self.hand.get_cards().get(0)
-                        == self.hand.get_cards().get(1)
+    // the dealer's first card is dealt face up
+    pub fn get_upcard(&self) -> Option<&Arc<Card>> {
+        self.hand.get_cards().first().filter(|_| self.is_face_up(0))
+    }
+
+    // the dealer only checks for blackjack under an ace or ten-value upcard,
+    // or on every hand when no card is showing
+    pub fn should_peek(&self) -> bool {
+        self.is_dealer()
+            && self.get_upcard().map_or(true, |card| {
+                card.rank == Rank::Ace || is_ten_value(card.rank)
+            })
+    }
+
+    pub fn get_insurance(&self) -> &Wager {
+        &self.insurance
+    }
+
+    pub fn get_insurance_value(&self) -> u32 {
+        self.insurance.get_wager()
+    }
+
+    pub fn place_insurance(&mut self, wager: u32) {
+        self.insurance.add_wager(wager);
+    }
+
+    pub fn reset_insurance(&mut self) {
+        self.insurance.reset_wager();
+    }
+
+    // insurance can only be taken once, on the first two cards of the hand
+    pub fn can_insure(&self) -> bool {
+        !self.is_dealer()
+            && !self.from_split
+            && !self.even_money
+            && self.hand.get_card_count() == 2
+            && self.insurance.get_wager() == 0
+            && self.result_state == ResultsState::Default
+    }
+
+    pub fn get_side_bets(&self) -> &Vec<SideBetStake> {
+        &self.side_bets
+    }
+
+    pub fn add_side_bet(&mut self, stake: SideBetStake) {
+        self.side_bets.push(stake);
+    }
+
+    pub fn get_side_bet_wagered_value(&self) -> u32 {
+        self.side_bets.iter().map(|stake| stake.get_wagered_value()).sum()
+    }
+
+    // settles and removes the side bets, returns the amount to pay
+    pub fn settle_side_bets(&mut self, dealer: &[Arc<Card>]) -> u32 {
+        let cards = self.hand.get_cards();
+
+        self.side_bets.drain(..).map(|stake| stake.settle(cards, dealer)).sum()
+    }
+
+    pub fn set_early_payout(&mut self, payout: u32) {
+        self.early_payout = Some(payout);
+    }
+
+    // the payout is only reported once, when the round is settled
+    pub fn take_early_payout(&mut self) -> Option<u32> {
+        self.early_payout.take()
+    }
+
+    pub fn is_even_money(&self) -> bool {
+        self.even_money
+    }
+
+    pub fn set_even_money(&mut self) {
+        self.even_money = true;
+    }
+
+    // the two hands of a blackjack switch player are linked until the cards
+    // have been switched
+    pub fn is_switch_linked(&self) -> bool {
+        self.switch_linked
+    }
+
+    pub fn set_switch_linked(&mut self, linked: bool) {
+        self.switch_linked = linked;
+    }
+
+    // a switched hand's 21 is not a natural
+    pub fn is_switched(&self) -> bool {
+        self.switched
+    }
+
+    pub fn set_switched(&mut self) {
+        self.switched = true;
+        self.switch_linked = false;
+    }
+
+    pub fn replace_card(
+        &mut self,
+        index: usize,
+        card: Arc<Card>,
+    ) -> Option<Arc<Card>> {
+        self.hand.replace_card(index, card)
+    }
+
+    pub fn is_split_hand(&self) -> bool {
+        self.from_split
+    }
+
+    pub fn is_split_stake(&self) -> bool {
+        self.split_stake
+    }
+
+    // split aces only receive a single card each
+    pub fn is_split_aces(&self) -> bool {
+        self.from_split
+            && self.hand.get_cards().first().map(|card| card.rank)
+                == Some(Rank::Ace)
+    }
+
+    // the max number of splits is limited by the player, not the hand
+    pub fn can_split(&self, rules: &TableRules) -> bool {
+        if self.is_dealer() || rules.max_splits == 0 {
+            return false;
+        }
+
+        if self.is_split_aces() && !rules.resplit_aces {
+            return false;
+        }
+
+        match self.hand.get_cards().as_slice() {
+            [first, second] => {
+                first.rank == second.rank
+                    || (rules.split_unlike_tens
+                        && is_ten_value(first.rank)
+                        && is_ten_value(second.rank))
             }
+            _ => false,
         }
     }
 
-    pub fn split(&mut self) -> Option<[BlackjackHand; 2]> {
-        if self.can_split() && !self.is_dealer() {
+    pub fn split(&mut self, rules: &TableRules) -> Option<[BlackjackHand; 2]> {
+        if self.can_split(rules) && !self.is_dealer() {
             let player_type = self.player_type.clone();
             let cards = self.hand.get_cards();
 
-            return Some([
+            let mut hands = [
                 BlackjackHand::with_cards(
                     player_type.clone(),
                     vec![cards.get(0).unwrap().clone()],
@@ -103,28 +316,207 @@ impl BlackjackHand {
                     player_type,
                     vec![cards.get(1).unwrap().clone()],
                 ),
-            ]);
+            ];
+            hands.iter_mut().for_each(|hand| {
+                hand.from_split = true;
+                hand.natural_after_split = rules.natural_after_split;
+            });
+
+            // the first hand keeps the stake of the hand that was split
+            hands[0].split_stake = self.split_stake;
+            hands[1].split_stake = true;
+
+            // the side bets stay with the first hand and are settled on the
+            // cards from before the split
+            for mut stake in self.side_bets.drain(..) {
+                stake.keep_cards(self.hand.get_cards());
+                hands[0].side_bets.push(stake);
+            }
+
+            return Some(hands);
         }
         None
     }
 
-    pub fn can_double_down(&self) -> bool {
-        match &self.player_type {
-            PlayerType::Dealer => false,
-            PlayerType::Player => self.hand.get_card_count() == 2,
+    pub fn is_doubled(&self) -> bool {
+        self.double_down_wager > 0 || self.free_double_down_wager > 0
+    }
+
+    // the part of the wager that was added when doubling down
+    pub fn get_double_down_wager(&self) -> u32 {
+        self.double_down_wager
+    }
+
+    // adds the extra wager, the hand is dealt a single card after doubling
+    pub fn double_down(&mut self, wager: u32) {
+        self.double_down_wager += wager;
+        self.wager.add_wager(wager);
+    }
+
+    // the house stakes the double
+    pub fn free_double_down(&mut self, wager: u32) {
+        self.free_double_down_wager += wager;
+        self.wager.add_free_wager(wager);
+    }
+
+    // free doubles are only on a hard 9, 10 or 11
+    pub fn can_free_double_down(&self, rules: &TableRules) -> bool {
+        rules.free_doubles
+            && self.hand.get_card_count() == 2
+            && self.can_double_down(rules)
+            && match self.get_total() {
+                Some(HandTotal::Hard(n)) => (9..=11).contains(&n),
+                _ => false,
+            }
+    }
+
+    // every pair can be split for free except ten-value cards
+    pub fn can_free_split(&self, rules: &TableRules) -> bool {
+        rules.free_splits
+            && self.can_split(rules)
+            && self
+                .get_cards()
+                .first()
+                .map_or(false, |card| !is_ten_value(card.rank))
+    }
+
+    pub fn can_double_down(&self, rules: &TableRules) -> bool {
+        // split aces only take the one card
+        if self.is_dealer() || self.is_doubled() || self.is_split_aces() {
+            return false;
+        }
+
+        // doubling after hitting is the same as a hit, so the hand has to be
+        // under 21
+        match self.hand.get_card_count() {
+            2 => {}
+            n if n > 2 && rules.double_any_number_of_cards => {
+                if self.best_total().map_or(true, |total| total >= 21)
+                    || self.is_charlie(rules)
+                {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+
+        if self.from_split && !rules.double_after_split {
+            return false;
+        }
+
+        match (&rules.double_down, self.get_total()) {
+            (DoubleDown::AnyTwoCards, _) => true,
+            (DoubleDown::NineToEleven, Some(HandTotal::Hard(n))) => {
+                (9..=11).contains(&n)
+            }
+            (DoubleDown::TenToEleven, Some(HandTotal::Hard(n))) => {
+                (10..=11).contains(&n)
+            }
+            _ => false,
+        }
+    }
+
+    // only the first two cards of an unsplit hand can be surrendered, late
+    // surrender is only offered once the dealer has checked for blackjack
+    pub fn can_surrender(
+        &self,
+        rules: &TableRules,
+        dealer_checked: bool,
+    ) -> bool {
+        if self.is_dealer() || self.from_split || self.is_doubled() {
+            return false;
+        }
+
+        if self.hand.get_card_count() != 2
+            || self.result_state != ResultsState::Default
+        {
+            return false;
+        }
+
+        match rules.surrender {
+            Surrender::None => false,
+            Surrender::Late => dealer_checked,
+            Surrender::Early => true,
+        }
+    }
+
+    // a doubled hand that hasn't busted can be rescued when the rules allow it
+    pub fn can_rescue(&self, rules: &TableRules) -> bool {
+        rules.double_down_rescue
+            && self.is_doubled()
+            && !self.is_busted()
+            && self.result_state == ResultsState::Default
+            && self.state != HandState::HandPlayed
+    }
+
+    // the part of the wager that was added when buying cards
+    pub fn get_bought_wager(&self) -> u32 {
+        self.bought_wager
+    }
+
+    pub fn buy(&mut self, wager: u32) {
+        self.bought_wager += wager;
+        self.wager.add_wager(wager);
+    }
+
+    // a card can only be bought for up to the original wager, before twisting
+    // and never as the fifth card
+    pub fn can_buy(&self, wager: u32, rules: &TableRules) -> bool {
+        let original = self.get_wagered_value() - self.bought_wager;
+
+        rules.buy_cards
+            && !self.twisted
+            && self.hand.get_card_count() >= 2
+            && self.hand.get_card_count() < 4
+            && wager > 0
+            && wager <= original
+            && self.can_hit(rules)
+    }
+
+    pub fn is_twisted(&self) -> bool {
+        self.twisted
+    }
+
+    pub fn set_twisted(&mut self) {
+        self.twisted = true;
+    }
+
+    // a hand that can still be hit has to reach the table's minimum to stand
+    pub fn can_stand(&self, rules: &TableRules) -> bool {
+        match (rules.min_stand_total, self.best_total()) {
+            (Some(min), Some(total)) => total >= min || !self.can_hit(rules),
+            _ => true,
+        }
+    }
+
+    // whether the player's hand reached the table's charlie without busting
+    pub fn is_charlie(&self, rules: &TableRules) -> bool {
+        match &rules.charlie {
+            Some(charlie) => {
+                !self.is_dealer()
+                    && self.hand.get_card_count() >= charlie.cards
+                    && self.best_total().is_some()
+            }
+            None => false,
         }
     }
 
-    pub fn can_hit(&self) -> bool {
+    pub fn can_hit(&self, rules: &TableRules) -> bool {
+        if self.is_split_aces() && self.hand.get_card_count() >= 2 {
+            return false;
+        }
+
+        if self.is_doubled() || self.is_charlie(rules) {
+            return false;
+        }
+
         match self.player_type {
-            PlayerType::Player => match self.get_value() {
-                Some(HandValue::V(n)) => n < 21,
-                Some(HandValue::Ace(_, high)) => high < 21,
-                _ => false,
-            },
-            PlayerType::Dealer => match self.get_value() {
-                Some(HandValue::V(n)) => n < 17,
-                Some(HandValue::Ace(low, high)) => low <= 17 || high < 17, // hit on soft 17
+            PlayerType::Player => self.best_total().map_or(false, |n| n < 21),
+            PlayerType::Dealer => match self.get_total() {
+                Some(HandTotal::Hard(n)) => n < 17,
+                Some(HandTotal::Soft(n)) => {
+                    n < 17 || (n == 17 && rules.dealer_hits_soft_17)
+                }
                 _ => false,
             },
         }
@@ -136,9 +528,30 @@ impl BlackjackHand {
 
     pub fn reset(&mut self) {
         self.hand.reset_cards();
+        self.face_down.clear();
         self.state = HandState::Default;
         self.result_state = ResultsState::Default;
         self.wager.reset_wager();
+        self.from_split = false;
+        self.natural_after_split = false;
+        self.double_down_wager = 0;
+        self.free_double_down_wager = 0;
+        self.insurance.reset_wager();
+        self.even_money = false;
+        self.switch_linked = false;
+        self.switched = false;
+        self.split_stake = false;
+        self.side_bets.clear();
+        self.bought_wager = 0;
+        self.twisted = false;
+        self.early_payout = None;
+    }
+}
+
+fn is_ten_value(rank: Rank) -> bool {
+    match CardValue::from_rank(rank) {
+        CardValue::Single(10) => true,
+        _ => false,
     }
 }
 
@@ -166,6 +579,10 @@ pub trait ResultState {
         self.set_result_state(ResultsState::Pushed);
     }
 
+    fn set_surrendered(&mut self) {
+        self.set_result_state(ResultsState::Surrendered);
+    }
+
     fn did_win(&self) -> bool {
         match self.get_result_state() {
             ResultsState::Won => true,
@@ -204,10 +621,17 @@ impl WithHandValue for BlackjackHand {
     fn get_cards(&self) -> &Vec<Arc<Card>> {
         self.hand.get_cards()
     }
+
+    // a split hand only counts as a natural when the rules allowed it, a
+    // switched hand never does
+    fn allows_blackjack(&self) -> bool {
+        (!self.from_split || self.natural_after_split) && !self.switched
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::blackjack::table_rules::Charlie;
     use crate::cards::{Card, Hand, Rank, Suit};
 
     use super::*;
@@ -226,8 +650,111 @@ mod tests {
         dealer.add_card(card);
         dealer.add_card(card2);
 
-        assert!(player.can_split());
-        assert!(!dealer.can_split());
+        let rules = TableRules::default();
+
+        assert!(player.can_split(&rules));
+        assert!(!dealer.can_split(&rules));
+
+        let rules = TableRules {
+            max_splits: 0,
+            ..Default::default()
+        };
+
+        assert!(!player.can_split(&rules));
+    }
+
+    #[test]
+    fn it_can_split_on_rank() {
+        let rules = TableRules::default();
+        let same_tens = TableRules {
+            split_unlike_tens: false,
+            ..Default::default()
+        };
+
+        let eights = BlackjackHand::with_cards(
+            PlayerType::Player,
+            vec![
+                Arc::new(Card::from(Suit::Spade, Rank::Eight)),
+                Arc::new(Card::from(Suit::Heart, Rank::Eight)),
+            ],
+        );
+        let king_queen = BlackjackHand::with_cards(
+            PlayerType::Player,
+            vec![
+                Arc::new(Card::from(Suit::Spade, Rank::King)),
+                Arc::new(Card::from(Suit::Spade, Rank::Queen)),
+            ],
+        );
+        let eight_nine = BlackjackHand::with_cards(
+            PlayerType::Player,
+            vec![
+                Arc::new(Card::from(Suit::Spade, Rank::Eight)),
+                Arc::new(Card::from(Suit::Spade, Rank::Nine)),
+            ],
+        );
+
+        assert!(eights.can_split(&rules));
+        assert!(king_queen.can_split(&rules));
+        assert!(!king_queen.can_split(&same_tens));
+        assert!(!eight_nine.can_split(&rules));
+    }
+
+    #[test]
+    fn split_aces_take_one_card() {
+        let ace = Arc::new(Card::from(Suit::Spade, Rank::Ace));
+        let mut hand = BlackjackHand::with_cards(
+            PlayerType::Player,
+            vec![ace.clone(), ace.clone()],
+        );
+
+        let rules = TableRules::default();
+        let resplit_aces = TableRules {
+            resplit_aces: true,
+            ..Default::default()
+        };
+
+        let [mut aces, _] = hand.split(&rules).unwrap();
+
+        assert!(aces.is_split_aces());
+        assert!(aces.can_hit(&rules));
+
+        aces.add_card(ace.clone());
+
+        assert!(!aces.can_hit(&rules));
+        assert!(!aces.can_double_down(&rules));
+        assert!(!aces.can_split(&rules));
+        assert!(aces.can_split(&resplit_aces));
+    }
+
+    #[test]
+    fn natural_after_split() {
+        let ace = Arc::new(Card::from(Suit::Spade, Rank::Ace));
+        let king = Arc::new(Card::from(Suit::Spade, Rank::King));
+
+        let natural = BlackjackHand::with_cards(
+            PlayerType::Player,
+            vec![ace.clone(), king.clone()],
+        );
+
+        let mut aces = BlackjackHand::with_cards(
+            PlayerType::Player,
+            vec![ace.clone(), ace],
+        );
+        let natural_after_split = TableRules {
+            natural_after_split: true,
+            ..Default::default()
+        };
+
+        let [mut split_hand, _] = aces.split(&TableRules::default()).unwrap();
+        let [mut split_natural, _] = aces.split(&natural_after_split).unwrap();
+
+        split_hand.add_card(king.clone());
+        split_natural.add_card(king);
+
+        assert!(natural.is_blackjack());
+        assert!(split_natural.is_blackjack());
+        assert!(!split_hand.is_blackjack());
+        assert_eq!(split_hand.get_total(), Some(HandTotal::Soft(21)));
     }
 
     #[test]
@@ -240,7 +767,7 @@ mod tests {
         hand.add_card(card1);
         hand.add_card(card2);
 
-        let hands = hand.split();
+        let hands = hand.split(&TableRules::default());
 
         assert!(hands.is_some());
 
@@ -255,6 +782,51 @@ mod tests {
         let card2 = hand2.get_cards().first().unwrap();
 
         assert_eq!(card1, card2);
+        assert!(hand1.is_split_hand());
+        assert!(hand2.is_split_hand());
+    }
+
+    #[test]
+    fn can_double_down_any_number_of_cards() {
+        let cards = vec![
+            Arc::new(Card::from(Suit::Club, Rank::Two)),
+            Arc::new(Card::from(Suit::Club, Rank::Three)),
+            Arc::new(Card::from(Suit::Club, Rank::Five)),
+        ];
+        let mut hand = BlackjackHand::with_cards(PlayerType::Player, cards);
+
+        assert!(!hand.can_double_down(&TableRules::default()));
+        assert!(hand.can_double_down(&TableRules::spanish_21()));
+
+        hand.add_card(Arc::new(Card::from(Suit::Club, Rank::King)));
+        hand.add_card(Arc::new(Card::from(Suit::Club, Rank::Ace)));
+
+        assert!(!hand.can_double_down(&TableRules::spanish_21()));
+    }
+
+    #[test]
+    fn can_free_double_and_split() {
+        let rules = TableRules::free_bet();
+        let hand = |first, second| {
+            let cards = vec![
+                Arc::new(Card::from(Suit::Club, first)),
+                Arc::new(Card::from(Suit::Heart, second)),
+            ];
+            BlackjackHand::with_cards(PlayerType::Player, cards)
+        };
+
+        assert!(hand(Rank::Five, Rank::Five).can_free_double_down(&rules));
+        assert!(hand(Rank::Six, Rank::Three).can_free_double_down(&rules));
+        assert!(!hand(Rank::Ace, Rank::Eight).can_free_double_down(&rules));
+        assert!(!hand(Rank::Six, Rank::Six).can_free_double_down(&rules));
+
+        assert!(hand(Rank::Six, Rank::Six).can_free_split(&rules));
+        assert!(hand(Rank::Ace, Rank::Ace).can_free_split(&rules));
+        assert!(!hand(Rank::King, Rank::King).can_free_split(&rules));
+        assert!(hand(Rank::King, Rank::King).can_split(&rules));
+        assert!(
+            !hand(Rank::Six, Rank::Six).can_free_split(&TableRules::default())
+        );
     }
 
     #[test]
@@ -269,8 +841,151 @@ mod tests {
         let dealer =
             BlackjackHand::with_cards(PlayerType::Dealer, vec![card1, card2]);
 
-        assert!(player.can_double_down());
-        assert!(!dealer.can_double_down());
+        let rules = TableRules::default();
+
+        assert!(player.can_double_down(&rules));
+        assert!(!dealer.can_double_down(&rules));
+    }
+
+    #[test]
+    fn doubled_hand_can_not_hit_or_double_again() {
+        let mut hand = BlackjackHand::with_cards(
+            PlayerType::Player,
+            vec![
+                Arc::new(Card::from(Suit::Club, Rank::Two)),
+                Arc::new(Card::from(Suit::Club, Rank::Three)),
+            ],
+        );
+        let rules = TableRules::default();
+        hand.set_wagered_value(10);
+
+        hand.double_down(10);
+
+        assert!(hand.is_doubled());
+        assert_eq!(hand.get_wagered_value(), 20);
+        assert!(!hand.can_hit(&rules));
+        assert!(!hand.can_double_down(&rules));
+    }
+
+    #[test]
+    fn can_double_down_with_restrictions() {
+        let nine_to_eleven = TableRules {
+            double_down: DoubleDown::NineToEleven,
+            ..Default::default()
+        };
+        let ten_to_eleven = TableRules {
+            double_down: DoubleDown::TenToEleven,
+            ..Default::default()
+        };
+
+        let nine = BlackjackHand::with_cards(
+            PlayerType::Player,
+            vec![
+                Arc::new(Card::from(Suit::Club, Rank::Four)),
+                Arc::new(Card::from(Suit::Club, Rank::Five)),
+            ],
+        );
+        let soft_nineteen = BlackjackHand::with_cards(
+            PlayerType::Player,
+            vec![
+                Arc::new(Card::from(Suit::Club, Rank::Ace)),
+                Arc::new(Card::from(Suit::Club, Rank::Eight)),
+            ],
+        );
+
+        assert!(nine.can_double_down(&nine_to_eleven));
+        assert!(!nine.can_double_down(&ten_to_eleven));
+        assert!(!soft_nineteen.can_double_down(&nine_to_eleven));
+        assert!(soft_nineteen.can_double_down(&TableRules::default()));
+    }
+
+    #[test]
+    fn can_double_down_after_split() {
+        let card = Arc::new(Card::from(Suit::Club, Rank::Five));
+        let mut hand = BlackjackHand::with_cards(
+            PlayerType::Player,
+            vec![card.clone(), card.clone()],
+        );
+
+        let rules = TableRules::default();
+        let no_das = TableRules {
+            double_after_split: false,
+            ..Default::default()
+        };
+
+        let [mut split_hand, _] = hand.split(&rules).unwrap();
+        split_hand.add_card(Arc::new(Card::from(Suit::Club, Rank::Six)));
+
+        assert!(split_hand.can_double_down(&rules));
+        assert!(!split_hand.can_double_down(&no_das));
+    }
+
+    #[test]
+    fn it_deals_hole_card_face_down() {
+        let mut hand = BlackjackHand::new(PlayerType::Dealer);
+        let ace = Arc::new(Card::from(Suit::Club, Rank::Ace));
+        let king = Arc::new(Card::from(Suit::Club, Rank::King));
+
+        hand.add_card(ace.clone());
+        hand.add_card_face_down(king.clone());
+
+        assert!(hand.is_face_up(0));
+        assert!(!hand.is_face_up(1));
+        assert_eq!(hand.get_face_up_cards(), vec![&ace]);
+        assert!(hand.is_blackjack());
+
+        assert_eq!(hand.reveal_cards(), vec![king]);
+        assert!(hand.is_face_up(1));
+        assert!(hand.reveal_cards().is_empty());
+    }
+
+    #[test]
+    fn dealer_peeks_under_ace_or_ten() {
+        let peek = |rank| {
+            let cards = vec![
+                Arc::new(Card::from(Suit::Club, rank)),
+                Arc::new(Card::from(Suit::Club, Rank::Six)),
+            ];
+            BlackjackHand::with_cards(PlayerType::Dealer, cards).should_peek()
+        };
+
+        assert!(peek(Rank::Ace));
+        assert!(peek(Rank::Queen));
+        assert!(peek(Rank::Ten));
+        assert!(!peek(Rank::Nine));
+    }
+
+    #[test]
+    fn can_surrender() {
+        let cards = vec![
+            Arc::new(Card::from(Suit::Club, Rank::Ten)),
+            Arc::new(Card::from(Suit::Club, Rank::Six)),
+        ];
+        let mut hand = BlackjackHand::with_cards(PlayerType::Player, cards);
+
+        let late = TableRules {
+            surrender: Surrender::Late,
+            ..Default::default()
+        };
+        let early = TableRules {
+            surrender: Surrender::Early,
+            ..Default::default()
+        };
+
+        assert!(!hand.can_surrender(&TableRules::default(), true));
+        assert!(!hand.can_surrender(&late, false));
+        assert!(hand.can_surrender(&late, true));
+        assert!(hand.can_surrender(&early, false));
+        assert!(hand.can_surrender(&early, true));
+
+        hand.set_surrendered();
+
+        assert!(!hand.can_surrender(&early, true));
+
+        hand.reset();
+        hand.add_card(Arc::new(Card::from(Suit::Club, Rank::Two)));
+
+        assert!(!hand.can_surrender(&early, true));
     }
 
     #[test]
@@ -280,13 +995,88 @@ mod tests {
 
         let mut player =
             BlackjackHand::with_cards(PlayerType::Player, vec![card1, card2]);
+        let rules = TableRules::default();
 
-        assert!(player.can_hit());
+        assert!(player.can_hit(&rules));
 
         let card3 = Arc::new(Card::from(Suit::Club, Rank::King));
         player.add_card(card3);
 
-        assert!(!player.can_hit());
+        assert!(!player.can_hit(&rules));
+    }
+
+    #[test]
+    fn can_not_hit_after_charlie() {
+        let cards = [Rank::Two, Rank::Three, Rank::Two, Rank::Four]
+            .iter()
+            .map(|rank| Arc::new(Card::from(Suit::Heart, *rank)))
+            .collect();
+        let mut player = BlackjackHand::with_cards(PlayerType::Player, cards);
+        let rules = TableRules {
+            charlie: Some(Charlie::new(5)),
+            ..Default::default()
+        };
+
+        assert!(player.can_hit(&rules));
+        assert!(!player.is_charlie(&rules));
+
+        player.add_card(Arc::new(Card::from(Suit::Club, Rank::Five)));
+
+        assert!(player.is_charlie(&rules));
+        assert!(!player.can_hit(&rules));
+        assert!(player.can_hit(&TableRules::default()));
+
+        player.add_card(Arc::new(Card::from(Suit::Club, Rank::King)));
+
+        assert!(!player.is_charlie(&rules));
+    }
+
+    #[test]
+    fn it_buys_up_to_original_wager() {
+        let rules = TableRules::pontoon();
+        let mut player = BlackjackHand::with_cards(
+            PlayerType::Player,
+            vec![
+                Arc::new(Card::from(Suit::Club, Rank::Two)),
+                Arc::new(Card::from(Suit::Club, Rank::Five)),
+            ],
+        );
+        player.set_wagered_value(10);
+
+        assert!(!player.can_buy(0, &rules));
+        assert!(!player.can_buy(11, &rules));
+        assert!(!player.can_buy(10, &TableRules::default()));
+        assert!(player.can_buy(10, &rules));
+
+        player.buy(10);
+        player.add_card(Arc::new(Card::from(Suit::Club, Rank::Three)));
+
+        assert_eq!(player.get_wagered_value(), 20);
+        assert!(player.can_buy(10, &rules));
+        assert!(!player.can_buy(20, &rules));
+
+        player.set_twisted();
+
+        assert!(!player.can_buy(10, &rules));
+    }
+
+    #[test]
+    fn it_requires_min_total_to_stand() {
+        let rules = TableRules::pontoon();
+        let mut player = BlackjackHand::with_cards(
+            PlayerType::Player,
+            vec![
+                Arc::new(Card::from(Suit::Club, Rank::Ten)),
+                Arc::new(Card::from(Suit::Club, Rank::Four)),
+            ],
+        );
+
+        assert!(!player.can_stand(&rules));
+        assert!(player.can_stand(&TableRules::default()));
+
+        player.add_card(Arc::new(Card::from(Suit::Club, Rank::Ace)));
+
+        assert!(player.can_stand(&rules));
     }
 
     #[test]
@@ -296,13 +1086,14 @@ mod tests {
 
         let mut dealer =
             BlackjackHand::with_cards(PlayerType::Dealer, vec![card1, card2]);
+        let rules = TableRules::default();
 
-        assert!(dealer.can_hit()); // 7
+        assert!(dealer.can_hit(&rules)); // 7
 
         let card3 = Arc::new(Card::from(Suit::Club, Rank::King));
         dealer.add_card(card3.clone());
 
-        assert!(!dealer.can_hit()); // 17
+        assert!(!dealer.can_hit(&rules)); // 17
 
         dealer.reset();
 
@@ -311,10 +1102,37 @@ mod tests {
 
         dealer.add_cards(vec![card1, card2]);
 
-        assert!(dealer.can_hit()); // soft 17
+        assert!(dealer.can_hit(&rules)); // soft 17
 
         dealer.add_card(card3);
 
-        assert!(!dealer.can_hit()); // hard 17
+        assert!(!dealer.can_hit(&rules)); // hard 17
+    }
+
+    #[test]
+    fn can_hit_dealer_stand_soft_17() {
+        let rules = TableRules {
+            dealer_hits_soft_17: false,
+            ..Default::default()
+        };
+
+        let mut dealer = BlackjackHand::with_cards(
+            PlayerType::Dealer,
+            vec![
+                Arc::new(Card::from(Suit::Club, Rank::Ace)),
+                Arc::new(Card::from(Suit::Club, Rank::Five)),
+            ],
+        );
+
+        assert!(dealer.can_hit(&rules)); // soft 16
+
+        dealer.add_card(Arc::new(Card::from(Suit::Club, Rank::Ace)));
+
+        assert!(!dealer.can_hit(&rules)); // soft 17
+        assert!(dealer.can_hit(&TableRules::default()));
+
+        dealer.add_card(Arc::new(Card::from(Suit::Club, Rank::Ace)));
+
+        assert!(!dealer.can_hit(&TableRules::default())); // soft 18
     }
 }
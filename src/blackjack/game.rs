@@ -5,8 +5,13 @@ use failure::{format_err, Error};
 
 use crate::blackjack::game::lifecycle::{Lifecycle, Phase};
 use crate::blackjack::game::state::State;
-use crate::blackjack::player::Player;
-use crate::cards::shuffleable::Shuffleable;
+use crate::blackjack::player::{ActionError, Player};
+use crate::blackjack::settlement::Settlement;
+use crate::blackjack::side_bet::SideBet;
+use crate::blackjack::table_rules::TableRules;
+use crate::cards::card::Card;
+use crate::cards::shoe::Shoe;
+use uuid::Uuid;
 
 pub mod lifecycle;
 pub mod state;
@@ -38,12 +43,48 @@ pub enum Action {
     AddPlayer(Player),
     CreateAndAddPlayer(String),
     AddFundsToPlayerBankroll(Arc<RwLock<Player>>, u32),
+    StartRound,
+    DealCards,
+    ChangeDealer,
+    Hit(Arc<RwLock<Player>>),
+    Stand(Arc<RwLock<Player>>),
+    Twist(Arc<RwLock<Player>>),
+    Stick(Arc<RwLock<Player>>),
+    Buy(Arc<RwLock<Player>>, u32),
+    Split(Arc<RwLock<Player>>),
+    DoubleDown(Arc<RwLock<Player>>),
+    DoubleForLess(Arc<RwLock<Player>>, u32),
+    Surrender(Arc<RwLock<Player>>),
+    Rescue(Arc<RwLock<Player>>),
+    PlaceSwitchWagers(Arc<RwLock<Player>>, u32),
+    PlaceSideBet(Arc<RwLock<Player>>, Box<dyn SideBet>, u32),
+    Switch(Arc<RwLock<Player>>),
+    Insurance(Arc<RwLock<Player>>, u32),
+    EvenMoney(Arc<RwLock<Player>>),
+    SettleInsurance,
+    Peek,
+    DealDealerSecondCard,
+    PlayDealer,
+    SettleRound,
+    EndRound,
 }
 
 // events sent to the UI
 pub enum Event {
     PlayerAdded(Result<Arc<RwLock<Player>>, Error>),
     SetPlayerBankroll(u32),
+    ShoeReshuffled,
+    // the dealer is showing an ace
+    InsuranceOffered,
+    // the round is over before the players act
+    DealerBlackjack,
+    HandSettled(Settlement),
+    // burned cards are never sent to the UI
+    CardDealt(Result<(Uuid, Arc<Card>), Error>),
+    // the dealer's hole card, the card is sent once it is revealed
+    HoleCardDealt(Uuid),
+    CardRevealed(Uuid, Arc<Card>),
+    ActionFailed(Error),
 }
 
 pub struct Channels {
@@ -83,8 +124,22 @@ impl<'s> Game<'s> {
         Default::default()
     }
 
+    pub fn with_shoe(shoe: Shoe) -> Game<'s> {
+        Game {
+            state: State::with_shoe(shoe),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_rules(rules: TableRules) -> Game<'s> {
+        Game {
+            state: State::with_rules(rules),
+            ..Default::default()
+        }
+    }
+
     pub fn setup(&mut self) {
-        self.state.shoe_mut().shuffle();
+        self.state.shoe_mut().reshuffle();
     }
 
     pub fn step(&mut self) {
@@ -121,6 +176,253 @@ impl<'s> Game<'s> {
                     player.get_available_funds(),
                 ));
             }
+            Action::StartRound => self.start_round(),
+            Action::DealCards => self.deal_cards(),
+            Action::ChangeDealer => self.change_dealer(),
+            Action::Hit(player) => {
+                let mut player = player.write().unwrap();
+                let result = self.state.hit(&mut player).map(|card| vec![card]);
+                self.emit_dealt_cards(player.get_id(), result);
+            }
+            Action::Stand(player) => {
+                if let Err(e) = player.write().unwrap().stand() {
+                    self.emit(Event::ActionFailed(e.into()));
+                }
+            }
+            Action::Twist(player) => {
+                let mut player = player.write().unwrap();
+                let result =
+                    self.state.twist(&mut player).map(|card| vec![card]);
+                self.emit_dealt_cards(player.get_id(), result);
+            }
+            Action::Stick(player) => {
+                let mut player = player.write().unwrap();
+                if let Err(e) = self.state.stick(&mut player) {
+                    self.emit(Event::ActionFailed(e.into()));
+                }
+            }
+            Action::Buy(player, wager) => {
+                let mut player = player.write().unwrap();
+                let result = self.state.buy(&mut player, wager);
+                self.emit_dealt_cards(player.get_id(), result.map(|c| vec![c]));
+                self.emit(Event::SetPlayerBankroll(
+                    player.get_available_funds(),
+                ));
+            }
+            Action::DoubleDown(player) => {
+                let mut player = player.write().unwrap();
+                let result = self.state.double_down(&mut player, None);
+                self.emit_dealt_cards(player.get_id(), result.map(|c| vec![c]));
+                self.emit(Event::SetPlayerBankroll(
+                    player.get_available_funds(),
+                ));
+            }
+            Action::DoubleForLess(player, wager) => {
+                let mut player = player.write().unwrap();
+                let result = self.state.double_down(&mut player, Some(wager));
+                self.emit_dealt_cards(player.get_id(), result.map(|c| vec![c]));
+                self.emit(Event::SetPlayerBankroll(
+                    player.get_available_funds(),
+                ));
+            }
+            Action::Surrender(player) => {
+                let mut player = player.write().unwrap();
+                match self.state.surrender(&mut player) {
+                    Ok(_) => self.emit(Event::SetPlayerBankroll(
+                        player.get_available_funds(),
+                    )),
+                    Err(e) => self.emit(Event::ActionFailed(e.into())),
+                }
+            }
+            Action::PlaceSwitchWagers(player, wager) => {
+                let mut player = player.write().unwrap();
+                match self.state.place_switch_wagers(&mut player, wager) {
+                    Ok(_) => self.emit(Event::SetPlayerBankroll(
+                        player.get_available_funds(),
+                    )),
+                    Err(e) => self.emit(Event::ActionFailed(e.into())),
+                }
+            }
+            Action::PlaceSideBet(player, bet, wager) => {
+                let mut player = player.write().unwrap();
+                match player.place_side_bet(bet, wager) {
+                    Ok(_) => self.emit(Event::SetPlayerBankroll(
+                        player.get_available_funds(),
+                    )),
+                    Err(e) => self.emit(Event::ActionFailed(e.into())),
+                }
+            }
+            Action::Switch(player) => {
+                if let Err(e) = player.write().unwrap().switch() {
+                    self.emit(Event::ActionFailed(e.into()));
+                }
+            }
+            Action::Rescue(player) => {
+                let mut player = player.write().unwrap();
+                match self.state.rescue(&mut player) {
+                    Ok(_) => self.emit(Event::SetPlayerBankroll(
+                        player.get_available_funds(),
+                    )),
+                    Err(e) => self.emit(Event::ActionFailed(e.into())),
+                }
+            }
+            Action::Insurance(_, _) | Action::EvenMoney(_)
+                if self.lifecycle.get_phase().curr() != &Phase::Insurance =>
+            {
+                self.emit(Event::ActionFailed(ActionError::NotAllowed.into()))
+            }
+            Action::Insurance(player, wager) => {
+                let mut player = player.write().unwrap();
+                match self.state.place_insurance(&mut player, wager) {
+                    Ok(_) => self.emit(Event::SetPlayerBankroll(
+                        player.get_available_funds(),
+                    )),
+                    Err(e) => self.emit(Event::ActionFailed(e.into())),
+                }
+            }
+            Action::EvenMoney(player) => {
+                let mut player = player.write().unwrap();
+                match self.state.take_even_money(&mut player) {
+                    Ok(_) => self.emit(Event::SetPlayerBankroll(
+                        player.get_available_funds(),
+                    )),
+                    Err(e) => self.emit(Event::ActionFailed(e.into())),
+                }
+            }
+            Action::Peek => {
+                self.lifecycle.set_phase(Phase::Playing);
+                let hidden = self.state.get_dealer_face_down_cards();
+                if self.state.peek() {
+                    self.emit_revealed_cards(hidden);
+                    self.emit(Event::DealerBlackjack);
+                }
+            }
+            Action::DealDealerSecondCard => {
+                match self.state.deal_dealer_second_card() {
+                    Ok(Some(card)) => self.emit(Event::CardDealt(Ok(card))),
+                    Ok(None) => {}
+                    Err(e) => self.emit(Event::CardDealt(Err(e.into()))),
+                }
+            }
+            Action::PlayDealer => {
+                let hidden = self.state.get_dealer_face_down_cards();
+                let result = self.state.play_dealer();
+                self.emit_revealed_cards(hidden);
+                self.emit_dealt_cards(self.state.get_dealer_id(), result);
+            }
+            Action::SettleRound => self.settle_round(),
+            Action::EndRound => self.state.end_round(),
+            Action::SettleInsurance => {
+                self.lifecycle.set_phase(Phase::Playing);
+                let hidden = self.state.get_dealer_face_down_cards();
+                let (dealer_natural, players) = self.state.settle_insurance();
+                if dealer_natural {
+                    self.emit_revealed_cards(hidden);
+                    self.emit(Event::DealerBlackjack);
+                }
+                players.iter().for_each(|player| {
+                    let player = player.read().unwrap();
+                    self.emit(Event::SetPlayerBankroll(
+                        player.get_available_funds(),
+                    ));
+                });
+            }
+            Action::Split(player) => {
+                let mut player = player.write().unwrap();
+                let result = self.state.split(&mut player);
+                self.emit_dealt_cards(player.get_id(), result);
+                self.emit(Event::SetPlayerBankroll(
+                    player.get_available_funds(),
+                ));
+            }
+        }
+    }
+
+    fn emit_dealt_cards(
+        &self,
+        player_id: Uuid,
+        result: Result<Vec<Arc<Card>>, ActionError>,
+    ) {
+        match result {
+            Ok(cards) => cards.into_iter().for_each(|card| {
+                self.emit(Event::CardDealt(Ok((player_id, card))));
+            }),
+            Err(e) => self.emit(Event::ActionFailed(e.into())),
+        }
+    }
+
+    // sends the dealer's cards that were face down and have been turned over
+    fn emit_revealed_cards(&self, hidden: Vec<Arc<Card>>) {
+        let dealer_id = self.state.get_dealer_id();
+        hidden
+            .into_iter()
+            .filter(|card| !self.state.is_dealer_card_face_down(card))
+            .for_each(|card| self.emit(Event::CardRevealed(dealer_id, card)));
+    }
+
+    // the ui is only told a face down card was dealt, not which card. the
+    // insurance window is open from the deal until the dealer checks the
+    // hole card
+    fn deal_cards(&mut self) {
+        self.lifecycle.set_phase(Phase::Dealing);
+
+        match self.state.deal_cards() {
+            Ok(dealt) => dealt.into_iter().for_each(|(id, card)| {
+                let face_down = id == self.state.get_dealer_id()
+                    && self.state.is_dealer_card_face_down(&card);
+
+                if face_down {
+                    self.emit(Event::HoleCardDealt(id));
+                } else {
+                    self.emit(Event::CardDealt(Ok((id, card))));
+                }
+            }),
+            Err(e) => return self.emit(Event::CardDealt(Err(e.into()))),
+        }
+
+        if self.state.is_insurance_offered() {
+            self.lifecycle.set_phase(Phase::Insurance);
+            self.emit(Event::InsuranceOffered);
+        } else {
+            self.lifecycle.set_phase(Phase::Playing);
+        }
+    }
+
+    // emits every settled hand, then the bankrolls of the players that were
+    // paid
+    fn settle_round(&mut self) {
+        self.lifecycle.set_phase(Phase::Results);
+        let settlements = self.state.settle_round();
+        let mut paid = vec![];
+
+        for settlement in settlements {
+            if !paid.contains(&settlement.player_id) {
+                paid.push(settlement.player_id);
+            }
+            self.emit(Event::HandSettled(settlement));
+        }
+
+        for id in paid {
+            if let Some(player) = self.state.get_player_by_id(id) {
+                let funds = player.read().unwrap().get_available_funds();
+                self.emit(Event::SetPlayerBankroll(funds));
+            }
+        }
+    }
+
+    // the new dealer starts from a fresh shoe once the cut card is out, e.g.
+    // when the burned card was the cut card
+    fn change_dealer(&mut self) {
+        if self.state.change_dealer() {
+            self.emit(Event::ShoeReshuffled);
+        }
+    }
+
+    fn start_round(&mut self) {
+        self.lifecycle.set_phase(Phase::Betting);
+
+        if self.state.start_round() {
+            self.emit(Event::ShoeReshuffled);
         }
     }
 
@@ -142,3 +444,237 @@ impl<'s> Game<'s> {
         self.channels.events.tx.send(event).unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::cards::rank::Rank;
+
+    use super::*;
+
+    #[test]
+    fn it_reshuffles_when_dealer_change_reaches_cut_card() {
+        let mut shoe = Shoe::with_decks(1);
+        shoe.set_penetration(0.0);
+        shoe.set_burn_on_dealer_change(true);
+
+        let mut game = Game::with_shoe(shoe);
+        game.setup();
+
+        let tx = game.get_actions_sender();
+        let rx = game.get_events_receiver();
+
+        tx.send(Action::ChangeDealer).unwrap();
+        game.step();
+
+        match rx.try_recv() {
+            Ok(Event::ShoeReshuffled) => {}
+            _ => panic!("Expected the shoe to be reshuffled"),
+        }
+    }
+
+    #[test]
+    fn it_only_takes_insurance_during_insurance_phase() {
+        // the dealer's ace is still out once the next round starts, but the
+        // insurance window has closed
+        for &next_round in &[false, true] {
+            let shoe = Shoe::from_str("10S AH 9D 7C");
+            let mut game = Game::with_shoe(shoe.unwrap());
+            game.setup();
+
+            let tx = game.get_actions_sender();
+            let rx = game.get_events_receiver();
+
+            tx.send(Action::CreateAndAddPlayer(String::from("Tester")))
+                .unwrap();
+            game.step();
+
+            let player = match rx.try_recv() {
+                Ok(Event::PlayerAdded(Ok(player))) => player,
+                _ => panic!("Expected the player to be added"),
+            };
+            player.write().unwrap().add_funds(100);
+            player.write().unwrap().place_wager(10).unwrap();
+
+            tx.send(Action::Insurance(player.clone(), 5)).unwrap();
+            tx.send(Action::DealCards).unwrap();
+            if next_round {
+                tx.send(Action::StartRound).unwrap();
+            }
+            tx.send(Action::Insurance(player.clone(), 5)).unwrap();
+            game.step();
+
+            let failed = rx
+                .try_iter()
+                .filter(|event| match event {
+                    Event::ActionFailed(_) => true,
+                    _ => false,
+                })
+                .count();
+
+            assert_eq!(
+                failed,
+                if next_round {
+                    2
+                } else {
+                    1
+                }
+            );
+            assert_eq!(
+                player.read().unwrap().get_available_funds(),
+                if next_round {
+                    90
+                } else {
+                    85
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn it_plays_consecutive_rounds() {
+        let shoe = Shoe::from_str("10S 9H 8D 8C KS 9D 7H 10C");
+        let mut game = Game::with_shoe(shoe.unwrap());
+        game.setup();
+
+        let tx = game.get_actions_sender();
+        let rx = game.get_events_receiver();
+
+        tx.send(Action::CreateAndAddPlayer(String::from("Tester"))).unwrap();
+        game.step();
+
+        let player = match rx.try_recv() {
+            Ok(Event::PlayerAdded(Ok(player))) => player,
+            _ => panic!("Expected the player to be added"),
+        };
+        player.write().unwrap().add_funds(100);
+
+        let card_count = |player: &Arc<RwLock<Player>>| {
+            let player = player.read().unwrap();
+            player.get_hands().first().map_or(0, |h| h.get_card_count())
+        };
+
+        for &(funds, discarded) in &[(110, 4), (100, 8)] {
+            tx.send(Action::StartRound).unwrap();
+            game.step();
+
+            assert!(player.write().unwrap().place_wager(10).is_ok());
+
+            tx.send(Action::DealCards).unwrap();
+            tx.send(Action::Stand(player.clone())).unwrap();
+            tx.send(Action::PlayDealer).unwrap();
+            tx.send(Action::SettleRound).unwrap();
+            game.step();
+
+            assert_eq!(card_count(&player), 2);
+            assert_eq!(player.read().unwrap().get_available_funds(), funds);
+
+            tx.send(Action::EndRound).unwrap();
+            game.step();
+
+            assert_eq!(card_count(&player), 0);
+            assert_eq!(
+                game.state.get_shoe().get_discarded_card_count(),
+                discarded
+            );
+        }
+
+        let shoe = game.state.get_shoe();
+        assert_eq!(shoe.get_discarded_rank_count(Rank::Nine), 2);
+        assert_eq!(shoe.get_remaining_card_count(), 0);
+
+        for event in rx.try_iter() {
+            if let Event::ActionFailed(e) = event {
+                panic!("Unexpected failed action: {}", e);
+            }
+        }
+    }
+
+    #[test]
+    fn it_hides_hole_card_until_revealed() {
+        let shoe = Shoe::from_str("10S 9H 6H 7D");
+        let mut game = Game::with_shoe(shoe.unwrap());
+        game.setup();
+
+        let tx = game.get_actions_sender();
+        let rx = game.get_events_receiver();
+
+        tx.send(Action::CreateAndAddPlayer(String::from("Tester"))).unwrap();
+        tx.send(Action::DealCards).unwrap();
+        tx.send(Action::PlayDealer).unwrap();
+        game.step();
+
+        let hole_cards = rx
+            .try_iter()
+            .filter_map(|event| match event {
+                Event::HoleCardDealt(_) => Some(None),
+                Event::CardRevealed(_, card) => Some(Some(card.to_string())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(hole_cards, vec![None, Some(String::from("\u{2666}7"))]);
+    }
+
+    #[test]
+    fn it_deals_both_dealer_cards_face_down_in_pontoon() {
+        let mut game = Game::with_rules(TableRules::pontoon());
+        game.setup();
+
+        let tx = game.get_actions_sender();
+        let rx = game.get_events_receiver();
+
+        tx.send(Action::CreateAndAddPlayer(String::from("Tester"))).unwrap();
+        tx.send(Action::DealCards).unwrap();
+        game.step();
+
+        let hole_cards = rx
+            .try_iter()
+            .filter(|event| match event {
+                Event::HoleCardDealt(_) => true,
+                _ => false,
+            })
+            .count();
+
+        assert_eq!(hole_cards, 2);
+    }
+
+    #[test]
+    fn it_deals_stacked_shoe_in_order() {
+        let shoe = Shoe::from_str("A\u{2660} 9\u{2665} K\u{2660} 7\u{2666}");
+        let mut game = Game::with_shoe(shoe.unwrap());
+        game.setup();
+
+        let tx = game.get_actions_sender();
+        let rx = game.get_events_receiver();
+
+        tx.send(Action::CreateAndAddPlayer(String::from("Tester"))).unwrap();
+        tx.send(Action::DealCards).unwrap();
+        game.step();
+
+        let player_id = match rx.try_recv() {
+            Ok(Event::PlayerAdded(Ok(player))) => {
+                player.read().unwrap().get_id()
+            }
+            _ => panic!("Expected the player to be added"),
+        };
+
+        let dealt: Vec<(Uuid, String)> = rx
+            .try_iter()
+            .map(|event| match event {
+                Event::CardDealt(Ok((id, card))) => (id, card.to_string()),
+                Event::HoleCardDealt(id) => (id, String::new()),
+                _ => panic!("Expected a card to be dealt"),
+            })
+            .collect();
+
+        assert_eq!(dealt.len(), 4);
+        assert_eq!(dealt[0], (player_id, String::from("\u{2660}A")));
+        assert_ne!(dealt[1].0, player_id);
+        assert_eq!(dealt[1].1, String::from("\u{2665}9"));
+        assert_eq!(dealt[2], (player_id, String::from("\u{2660}K")));
+        // the hole card stays hidden from the ui
+        assert_eq!(dealt[3], (dealt[1].0, String::new()));
+    }
+}
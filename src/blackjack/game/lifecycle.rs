@@ -8,6 +8,7 @@ pub enum Phase {
     Initial,
     Betting,
     Dealing,
+    Insurance,
     Playing,
     Results,
 }
@@ -34,11 +35,12 @@ impl LifecyclePhase {
         Default::default()
     }
 
-    pub fn phases() -> [Phase; 5] {
+    pub fn phases() -> [Phase; 6] {
         [
             Phase::Initial,
             Phase::Betting,
             Phase::Dealing,
+            Phase::Insurance,
             Phase::Playing,
             Phase::Results,
         ]
@@ -59,6 +61,15 @@ impl LifecyclePhase {
         &self.prev
     }
 
+    // jumps straight to the phase, e.g. skipping insurance when it isn't
+    // offered
+    pub fn set(&mut self, phase: Phase) {
+        self.index =
+            Self::phases().iter().position(|p| p == &phase).unwrap_or(0);
+        self.prev = Some(self.curr.to_owned());
+        self.curr = phase;
+    }
+
     pub fn reset(&mut self) {
         self.prev = None;
         self.curr = Phase::Initial;
@@ -111,6 +122,10 @@ impl Lifecycle {
     pub fn next_phase(&mut self) -> Option<Phase> {
         self.phase.next()
     }
+
+    pub fn set_phase(&mut self, phase: Phase) {
+        self.phase.set(phase)
+    }
 }
 
 #[cfg(test)]
@@ -138,4 +153,26 @@ mod tests {
             Phase::Initial
         );
     }
+
+    #[test]
+    fn does_offer_insurance_between_dealing_and_playing() {
+        let mut lifecycle = Lifecycle::new();
+
+        lifecycle.next_phase();
+        lifecycle.next_phase();
+
+        assert_eq!(lifecycle.next_phase(), Some(Phase::Insurance));
+        assert_eq!(lifecycle.next_phase(), Some(Phase::Playing));
+    }
+
+    #[test]
+    fn does_set_phase() {
+        let mut lifecycle = Lifecycle::new();
+
+        lifecycle.set_phase(Phase::Playing);
+
+        assert_eq!(lifecycle.get_phase().curr(), &Phase::Playing);
+        assert_eq!(lifecycle.get_phase().prev(), &Some(Phase::Initial));
+        assert_eq!(lifecycle.next_phase(), Some(Phase::Results));
+    }
 }
@@ -4,7 +4,12 @@ use std::sync::{Arc, RwLock};
 use failure::{format_err, Error};
 use uuid::Uuid;
 
-use crate::blackjack::player::Player;
+use crate::blackjack::blackjack_hand::BlackjackHand;
+use crate::blackjack::player::{ActionError, Player};
+use crate::blackjack::settlement::Settlement;
+use crate::blackjack::table::{DealingError, Table};
+use crate::blackjack::table_rules::TableRules;
+use crate::cards::card::Card;
 use crate::cards::shoe::Shoe;
 
 pub enum Status {
@@ -17,18 +22,18 @@ struct Round<'r> {
 
 pub struct State<'r> {
     players: Vec<Arc<RwLock<Player>>>,
-    dealer: Player,
-    shoe: Shoe,
+    table: Table,
     status: Status,
     round: Option<Round<'r>>,
 }
 
 impl<'r> Default for State<'r> {
     fn default() -> State<'r> {
+        let rules = TableRules::default();
+
         State {
             players: vec![],
-            dealer: Player::new_dealer(String::from("Dealer")),
-            shoe: Shoe::with_decks(6),
+            table: Table::new(rules.clone(), Shoe::with_decks(rules.decks)),
             status: Status::INITIAL,
             round: None,
         }
@@ -40,8 +45,180 @@ impl<'r> State<'r> {
         Default::default()
     }
 
+    pub fn with_shoe(shoe: Shoe) -> State<'r> {
+        State {
+            table: Table::new(TableRules::default(), shoe),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_rules(rules: TableRules) -> State<'r> {
+        let shoe = Shoe::with_deck_spec(rules.decks, rules.deck.clone());
+
+        State {
+            table: Table::new(rules, shoe),
+            ..Default::default()
+        }
+    }
+
+    pub fn get_rules(&self) -> &TableRules {
+        self.table.get_rules()
+    }
+
+    pub fn get_shoe(&self) -> &Shoe {
+        self.table.get_shoe()
+    }
+
     pub fn shoe_mut(&mut self) -> &mut Shoe {
-        &mut self.shoe
+        self.table.shoe_mut()
+    }
+
+    pub fn change_dealer(&mut self) -> bool {
+        self.table.change_dealer()
+    }
+
+    pub fn start_round(&mut self) -> bool {
+        self.table.start_round()
+    }
+
+    pub fn peek(&mut self) -> bool {
+        self.with_players(|table, players| table.peek(players))
+    }
+
+    pub fn deal_dealer_second_card(
+        &mut self,
+    ) -> Result<Option<(Uuid, Arc<Card>)>, DealingError> {
+        let dealer_id = self.get_dealer_id();
+        let dealt = self.with_players(|table, players| {
+            table.deal_dealer_second_card(players)
+        })?;
+
+        Ok(dealt.map(|card| (dealer_id, card)))
+    }
+
+    pub fn deal_cards(
+        &mut self,
+    ) -> Result<Vec<(Uuid, Arc<Card>)>, DealingError> {
+        self.with_players(|table, players| table.deal_cards(players))
+    }
+
+    pub fn hit(
+        &mut self,
+        player: &mut Player,
+    ) -> Result<Arc<Card>, ActionError> {
+        self.table.hit(player)
+    }
+
+    pub fn twist(
+        &mut self,
+        player: &mut Player,
+    ) -> Result<Arc<Card>, ActionError> {
+        self.table.twist(player)
+    }
+
+    pub fn stick(&mut self, player: &mut Player) -> Result<(), ActionError> {
+        self.table.stick(player)
+    }
+
+    pub fn buy(
+        &mut self,
+        player: &mut Player,
+        wager: u32,
+    ) -> Result<Arc<Card>, ActionError> {
+        self.table.buy(player, wager)
+    }
+
+    pub fn double_down(
+        &mut self,
+        player: &mut Player,
+        wager: Option<u32>,
+    ) -> Result<Arc<Card>, ActionError> {
+        match wager {
+            Some(wager) => self.table.double_for_less(player, wager),
+            None => self.table.double_down(player),
+        }
+    }
+
+    pub fn is_insurance_offered(&self) -> bool {
+        self.table.is_insurance_offered()
+    }
+
+    pub fn place_insurance(
+        &mut self,
+        player: &mut Player,
+        wager: u32,
+    ) -> Result<(), ActionError> {
+        self.table.place_insurance(player, wager)
+    }
+
+    pub fn take_even_money(
+        &mut self,
+        player: &mut Player,
+    ) -> Result<u32, ActionError> {
+        self.table.take_even_money(player)
+    }
+
+    // checks the hole card and pays out the insurance bets, the players are
+    // returned so their bankrolls can be sent to the ui
+    pub fn settle_insurance(&mut self) -> (bool, Vec<Arc<RwLock<Player>>>) {
+        let dealer_natural =
+            self.with_players(|table, players| table.settle_insurance(players));
+        (dealer_natural, self.players.clone())
+    }
+
+    pub fn get_dealer_hand(&self) -> Option<&BlackjackHand> {
+        self.table.get_dealer_hand()
+    }
+
+    pub fn is_dealer_card_face_down(&self, card: &Arc<Card>) -> bool {
+        self.table.is_dealer_card_face_down(card)
+    }
+
+    pub fn get_dealer_face_down_cards(&self) -> Vec<Arc<Card>> {
+        self.table.get_dealer_face_down_cards()
+    }
+
+    pub fn play_dealer(&mut self) -> Result<Vec<Arc<Card>>, ActionError> {
+        self.with_players(|table, players| table.play_dealer(players))
+    }
+
+    pub fn settle_round(&mut self) -> Vec<Settlement> {
+        self.with_players(|table, players| table.settle_round(players))
+    }
+
+    // collects the cards from the finished round and clears every hand
+    pub fn end_round(&mut self) {
+        self.with_players(|table, players| table.end_round(players))
+    }
+
+    pub fn get_dealer_id(&self) -> Uuid {
+        self.table.get_dealer_id()
+    }
+
+    pub fn place_switch_wagers(
+        &mut self,
+        player: &mut Player,
+        wager: u32,
+    ) -> Result<(), ActionError> {
+        self.table.place_switch_wagers(player, wager)
+    }
+
+    pub fn rescue(&mut self, player: &mut Player) -> Result<u32, ActionError> {
+        self.table.rescue(player)
+    }
+
+    pub fn surrender(
+        &mut self,
+        player: &mut Player,
+    ) -> Result<u32, ActionError> {
+        self.table.surrender(player)
+    }
+
+    pub fn split(
+        &mut self,
+        player: &mut Player,
+    ) -> Result<Vec<Arc<Card>>, ActionError> {
+        self.table.split(player)
     }
 
     pub fn add_player(&mut self, player: Player) {
@@ -82,4 +259,17 @@ impl<'r> State<'r> {
             .iter()
             .position(|p| p.read().unwrap().get_id() == player_id)
     }
+
+    // locks every player for the table actions that involve the whole table
+    fn with_players<T>(
+        &mut self,
+        f: impl FnOnce(&mut Table, &mut [&mut Player]) -> T,
+    ) -> T {
+        let mut guards: Vec<_> =
+            self.players.iter().map(|p| p.write().unwrap()).collect();
+        let mut players: Vec<&mut Player> =
+            guards.iter_mut().map(|guard| &mut **guard).collect();
+
+        f(&mut self.table, &mut players)
+    }
 }
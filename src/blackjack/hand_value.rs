@@ -1,4 +1,5 @@
 use crate::cards::card::Card;
+use crate::cards::compact::RankCounts;
 use crate::cards::hand::Hand;
 use crate::cards::rank::Rank;
 use std::sync::Arc;
@@ -8,17 +9,58 @@ pub enum CardValue {
     Ace(u8, u8),
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Clone, Copy, Debug)]
 pub enum HandValue {
     V(u8),
     Ace(u8, u8),
     Bust(u8),
 }
 
-pub trait WithHandValue {
-    fn get_cards(&self) -> &Vec<Arc<Card>>;
+// the value of a hand as the rules see it, a soft total counts an ace as
+// eleven and a blackjack is only ever the first two cards
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum HandTotal {
+    Hard(u8),
+    Soft(u8),
+    Blackjack,
+    Bust(u8),
+}
 
-    fn rank_value(rank: Rank) -> CardValue {
+impl HandTotal {
+    pub fn from_value(
+        value: HandValue,
+        card_count: usize,
+        allows_blackjack: bool,
+    ) -> HandTotal {
+        match value {
+            HandValue::Ace(_, 21) if card_count == 2 && allows_blackjack => {
+                HandTotal::Blackjack
+            }
+            HandValue::Ace(_, high) => HandTotal::Soft(high),
+            HandValue::V(n) => HandTotal::Hard(n),
+            HandValue::Bust(n) => HandTotal::Bust(n),
+        }
+    }
+
+    // the highest total that doesn't bust
+    pub fn best_total(&self) -> Option<u8> {
+        match *self {
+            HandTotal::Hard(n) | HandTotal::Soft(n) => Some(n),
+            HandTotal::Blackjack => Some(21),
+            HandTotal::Bust(_) => None,
+        }
+    }
+
+    pub fn is_soft(&self) -> bool {
+        match self {
+            HandTotal::Soft(_) | HandTotal::Blackjack => true,
+            _ => false,
+        }
+    }
+}
+
+impl CardValue {
+    pub fn from_rank(rank: Rank) -> CardValue {
         match rank {
             Rank::Ace => CardValue::Ace(1, 11),
             Rank::Two => CardValue::Single(2),
@@ -35,67 +77,84 @@ pub trait WithHandValue {
             Rank::King => CardValue::Single(10),
         }
     }
+}
 
-    fn get_value(&self) -> Option<HandValue> {
-        if self.get_cards().len() == 0 {
-            return None;
+impl HandValue {
+    // values the ranks without allocating, every ace counts as one and a single
+    // ace is counted as eleven when it doesn't bust the hand. the value stops
+    // changing once the hand busts
+    pub fn from_ranks<I>(ranks: I) -> Option<HandValue>
+    where
+        I: IntoIterator<Item = Rank>,
+    {
+        let mut total: u8 = 0;
+        let mut has_ace = false;
+        let mut is_empty = true;
+
+        for rank in ranks {
+            is_empty = false;
+
+            match CardValue::from_rank(rank) {
+                CardValue::Single(v) => total += v,
+                CardValue::Ace(low, _) => {
+                    total += low;
+                    has_ace = true;
+                }
+            }
+
+            if total > 21 {
+                return Some(HandValue::Bust(total));
+            }
         }
 
-        Some(
-            self.get_cards()
-                .iter()
-                .map(|card| Self::rank_value(card.rank))
-                .fold(HandValue::V(0), |curr, val| match (curr, val) {
-                    (HandValue::V(acc), CardValue::Single(v)) => {
-                        self.derive_value(vec![acc + v])
-                    }
-                    (HandValue::V(acc), CardValue::Ace(v1, v2)) => {
-                        self.derive_value(vec![acc + v1, acc + v2])
-                    }
-                    (HandValue::Ace(acc1, acc2), CardValue::Single(v)) => {
-                        self.derive_value(vec![acc1 + v, acc2 + v])
-                    }
-                    (HandValue::Ace(acc1, acc2), CardValue::Ace(v1, v2)) => {
-                        self.derive_value(vec![
-                            acc1 + v1,
-                            acc1 + v2,
-                            acc2 + v1,
-                            acc2 + v2,
-                        ])
-                    }
-                    (HandValue::Bust(n), _) => HandValue::Bust(n),
-                    _ => unreachable!(""),
-                }),
-        )
+        match (is_empty, has_ace && total + 10 <= 21) {
+            (true, _) => None,
+            (false, true) => Some(HandValue::Ace(total, total + 10)),
+            (false, false) => Some(HandValue::V(total)),
+        }
+    }
+
+    pub fn from_rank_counts(counts: &RankCounts) -> Option<HandValue> {
+        HandValue::from_ranks(counts.ranks())
+    }
+}
+
+pub trait WithHandValue {
+    fn get_cards(&self) -> &Vec<Arc<Card>>;
+
+    fn rank_value(rank: Rank) -> CardValue {
+        CardValue::from_rank(rank)
     }
 
-    fn derive_value(&self, mut values: Vec<u8>) -> HandValue {
-        values.sort_unstable();
-        values.dedup();
+    fn get_value(&self) -> Option<HandValue> {
+        HandValue::from_ranks(self.get_cards().iter().map(|card| card.rank))
+    }
 
-        let less_than_21 = values.clone();
-        let less_than_21: Vec<&u8> =
-            less_than_21.iter().filter(|v| **v <= 21).collect();
+    // hands that can never be a blackjack, e.g. after a split, count two-card
+    // 21 as a regular soft 21
+    fn allows_blackjack(&self) -> bool {
+        true
+    }
 
-        if let (Some(n1), Some(n2)) = (less_than_21.get(0), less_than_21.get(1))
-        {
-            return HandValue::Ace(**n1, **n2);
-        }
+    fn get_total(&self) -> Option<HandTotal> {
+        let value = self.get_value()?;
+        Some(HandTotal::from_value(
+            value,
+            self.get_cards().len(),
+            self.allows_blackjack(),
+        ))
+    }
 
-        if let Some(n1) = less_than_21.get(0) {
-            return HandValue::V(**n1);
-        }
+    fn best_total(&self) -> Option<u8> {
+        self.get_total().and_then(|total| total.best_total())
+    }
 
-        if values.len() == 1 {
-            if let Some(n) = values.get(0) {
-                return HandValue::Bust(*n);
-            }
-        }
+    fn is_soft(&self) -> bool {
+        self.get_total().map_or(false, |total| total.is_soft())
+    }
 
-        unreachable!(format!(
-            "Failed to get value from cards 0 >= n >= 3 {:?}",
-            values
-        ));
+    fn is_blackjack(&self) -> bool {
+        self.get_total() == Some(HandTotal::Blackjack)
     }
 
     fn has_ace(&self) -> bool {
@@ -263,6 +322,68 @@ mod tests {
         assert_eq!(hand.get_value().unwrap(), HandValue::Ace(9, 19));
     }
 
+    #[test]
+    fn test_does_not_panic_adding_ace_to_21() {
+        let hand = Hand::with_cards(vec![
+            Arc::from(Card::from(Suit::Club, Rank::Ten)),
+            Arc::from(Card::from(Suit::Club, Rank::Five)),
+            Arc::from(Card::from(Suit::Club, Rank::Six)),
+            Arc::from(Card::from(Suit::Club, Rank::Ace)),
+        ]);
+
+        assert_eq!(hand.get_value().unwrap(), HandValue::Bust(22));
+    }
+
+    #[test]
+    fn test_rank_counts_match_cards() {
+        let hand = Hand::with_cards(vec![
+            Arc::from(Card::from(Suit::Club, Rank::Ace)),
+            Arc::from(Card::from(Suit::Heart, Rank::Ace)),
+            Arc::from(Card::from(Suit::Club, Rank::Nine)),
+        ]);
+
+        let counts = RankCounts::from_cards(hand.get_cards());
+
+        assert_eq!(HandValue::from_rank_counts(&counts), hand.get_value());
+        assert_eq!(
+            HandValue::from_rank_counts(&counts),
+            Some(HandValue::Ace(11, 21))
+        );
+        assert!(HandValue::from_rank_counts(&RankCounts::new()).is_none());
+    }
+
+    #[test]
+    fn test_blackjack_is_two_card_21() {
+        let ace = Arc::new(Card::from(Suit::Club, Rank::Ace));
+        let king = Arc::new(Card::from(Suit::Club, Rank::King));
+        let five = Arc::new(Card::from(Suit::Club, Rank::Five));
+
+        let hand = Hand::with_cards(vec![ace.clone(), king.clone()]);
+        assert!(hand.is_blackjack());
+        assert!(hand.is_soft());
+        assert_eq!(hand.best_total(), Some(21));
+
+        let hand = Hand::with_cards(vec![ace, five.clone(), five.clone()]);
+        assert!(!hand.is_blackjack());
+        assert_eq!(hand.get_total(), Some(HandTotal::Soft(21)));
+
+        let hand = Hand::with_cards(vec![king.clone(), five.clone(), five]);
+        assert_eq!(hand.get_total(), Some(HandTotal::Hard(20)));
+        assert!(!hand.is_soft());
+
+        let hand = Hand::with_cards(vec![king.clone(), king.clone(), king]);
+        assert_eq!(hand.get_total(), Some(HandTotal::Bust(30)));
+        assert_eq!(hand.best_total(), None);
+    }
+
+    #[test]
+    fn test_total_without_blackjack() {
+        let value = HandValue::Ace(11, 21);
+
+        assert_eq!(HandTotal::from_value(value, 2, true), HandTotal::Blackjack);
+        assert_eq!(HandTotal::from_value(value, 2, false), HandTotal::Soft(21));
+    }
+
     #[test]
     fn test_empty_is_none() {
         let hand = Hand::new();
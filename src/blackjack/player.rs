@@ -1,11 +1,31 @@
 use std::sync::Arc;
 
-use failure::{format_err, Error};
+use failure::{format_err, Error, Fail};
 use uuid::Uuid;
 
-use crate::blackjack::blackjack_hand::BlackjackHand;
+use crate::blackjack::blackjack_hand::{BlackjackHand, HandState, ResultState};
+use crate::blackjack::hand_value::WithHandValue;
+use crate::blackjack::settlement::{settle_hand, Settlement};
+use crate::blackjack::side_bet::{SideBet, SideBetStake};
+use crate::blackjack::table_rules::TableRules;
+use crate::blackjack::wager::WithWager;
 use crate::cards::bankroll::Bankroll;
 use crate::cards::card::Card;
+use crate::cards::shoe::Shoe;
+
+#[derive(Debug, Fail, PartialEq)]
+pub enum ActionError {
+    #[fail(display = "Player does not exist")]
+    PlayerNotFound,
+    #[fail(display = "Player does not have a hand to play")]
+    NoCurrentHand,
+    #[fail(display = "Action is not allowed for the current hand")]
+    NotAllowed,
+    #[fail(display = "Insufficient funds")]
+    InsufficientFunds,
+    #[fail(display = "Failed to take card from shoe")]
+    TakingCardFromShoe,
+}
 
 #[derive(PartialEq, Clone)]
 pub enum PlayerType {
@@ -18,6 +38,7 @@ pub struct Player {
     bankroll: Bankroll,
     hands: Vec<BlackjackHand>,
     current_hand: usize,
+    splits: usize,
     name: String,
     player_type: PlayerType,
 }
@@ -29,6 +50,7 @@ impl Player {
             bankroll: Bankroll::new(),
             hands: vec![BlackjackHand::new(PlayerType::Player)],
             current_hand: 0,
+            splits: 0,
             player_type: PlayerType::Player,
             name,
         }
@@ -40,6 +62,7 @@ impl Player {
             bankroll: Bankroll::new(),
             hands: vec![BlackjackHand::new(PlayerType::Dealer)],
             current_hand: 0,
+            splits: 0,
             player_type: PlayerType::Dealer,
             name,
         }
@@ -57,6 +80,566 @@ impl Player {
         self.hands.get(self.current_hand)
     }
 
+    pub fn get_current_hand_mut(&mut self) -> Option<&mut BlackjackHand> {
+        self.hands.get_mut(self.current_hand)
+    }
+
+    pub fn get_current_hand_index(&self) -> usize {
+        self.current_hand
+    }
+
+    pub fn get_hands(&self) -> &Vec<BlackjackHand> {
+        &self.hands
+    }
+
+    // true once every hand has been played
+    pub fn is_done(&self) -> bool {
+        self.current_hand >= self.hands.len()
+    }
+
+    // moves the wager from the bankroll onto the current hand
+    pub fn place_wager(&mut self, wager: u32) -> Result<(), ActionError> {
+        let hand = self
+            .hands
+            .get_mut(self.current_hand)
+            .ok_or(ActionError::NoCurrentHand)?;
+
+        self.bankroll
+            .sub_funds(wager)
+            .map_err(|_| ActionError::InsufficientFunds)?;
+
+        let wagered = hand.get_wagered_value();
+        hand.set_wagered_value(wagered + wager);
+
+        Ok(())
+    }
+
+    // blackjack switch players wager the same amount on two linked hands
+    pub fn place_switch_wagers(
+        &mut self,
+        wager: u32,
+        rules: &TableRules,
+    ) -> Result<(), ActionError> {
+        if !rules.switch_hands || self.hands.len() != 1 {
+            return Err(ActionError::NotAllowed);
+        }
+
+        if self.hands[0].get_card_count() > 0 {
+            return Err(ActionError::NotAllowed);
+        }
+
+        let stake =
+            wager.checked_mul(2).ok_or(ActionError::InsufficientFunds)?;
+
+        self.bankroll
+            .sub_funds(stake)
+            .map_err(|_| ActionError::InsufficientFunds)?;
+
+        let mut second = BlackjackHand::new(self.player_type.clone());
+        second.set_wagered_value(wager);
+        second.set_switch_linked(true);
+
+        let first = &mut self.hands[0];
+        let wagered = first.get_wagered_value();
+        first.set_wagered_value(wagered + wager);
+        first.set_switch_linked(true);
+
+        self.hands.push(second);
+
+        Ok(())
+    }
+
+    // swaps the second cards of the linked hands, only before either is played
+    pub fn switch(&mut self) -> Result<(), ActionError> {
+        if self.current_hand != 0 || self.hands.len() != 2 {
+            return Err(ActionError::NotAllowed);
+        }
+
+        let can_switch = self
+            .hands
+            .iter()
+            .all(|hand| hand.is_switch_linked() && hand.get_card_count() == 2);
+
+        if !can_switch {
+            return Err(ActionError::NotAllowed);
+        }
+
+        let second = self.hands[1].get_cards()[1].clone();
+        let first = self.hands[0]
+            .replace_card(1, second)
+            .ok_or(ActionError::NotAllowed)?;
+        self.hands[1].replace_card(1, first);
+
+        self.hands.iter_mut().for_each(|hand| hand.set_switched());
+
+        Ok(())
+    }
+
+    pub fn hit(
+        &mut self,
+        shoe: &mut Shoe,
+        rules: &TableRules,
+    ) -> Result<Arc<Card>, ActionError> {
+        let hand = self
+            .hands
+            .get_mut(self.current_hand)
+            .ok_or(ActionError::NoCurrentHand)?;
+
+        if !hand.can_hit(rules) {
+            return Err(ActionError::NotAllowed);
+        }
+
+        let card = shoe.take_card().ok_or(ActionError::TakingCardFromShoe)?;
+        hand.add_card(card.clone());
+
+        Ok(card)
+    }
+
+    // pontoon's hit, no more cards can be bought afterwards
+    pub fn twist(
+        &mut self,
+        shoe: &mut Shoe,
+        rules: &TableRules,
+    ) -> Result<Arc<Card>, ActionError> {
+        let card = self.hit(shoe, rules)?;
+
+        if let Some(hand) = self.hands.get_mut(self.current_hand) {
+            hand.set_twisted();
+        }
+
+        Ok(card)
+    }
+
+    // raises the stake and deals a card, the hand can still be played on
+    pub fn buy(
+        &mut self,
+        wager: u32,
+        shoe: &mut Shoe,
+        rules: &TableRules,
+    ) -> Result<Arc<Card>, ActionError> {
+        let hand = self
+            .hands
+            .get_mut(self.current_hand)
+            .ok_or(ActionError::NoCurrentHand)?;
+
+        if !hand.can_buy(wager, rules) {
+            return Err(ActionError::NotAllowed);
+        }
+
+        if shoe.get_remaining_card_count() == 0 {
+            return Err(ActionError::TakingCardFromShoe);
+        }
+
+        self.bankroll
+            .sub_funds(wager)
+            .map_err(|_| ActionError::InsufficientFunds)?;
+
+        let card = shoe.take_card().ok_or(ActionError::TakingCardFromShoe)?;
+        hand.buy(wager);
+        hand.add_card(card.clone());
+
+        Ok(card)
+    }
+
+    // pontoon's stand, the hand has to reach the table's minimum total
+    pub fn stick(&mut self, rules: &TableRules) -> Result<(), ActionError> {
+        let hand = self
+            .hands
+            .get(self.current_hand)
+            .ok_or(ActionError::NoCurrentHand)?;
+
+        if !hand.can_stand(rules) {
+            return Err(ActionError::NotAllowed);
+        }
+
+        self.stand()
+    }
+
+    // finishes the current hand and moves on to the next one
+    pub fn stand(&mut self) -> Result<(), ActionError> {
+        let hand = self
+            .hands
+            .get_mut(self.current_hand)
+            .ok_or(ActionError::NoCurrentHand)?;
+
+        hand.set_state(HandState::HandPlayed);
+        self.current_hand += 1;
+
+        Ok(())
+    }
+
+    // finishes every hand that has not been played yet, e.g. when the dealer
+    // has blackjack
+    pub fn end_turn(&mut self) {
+        self.hands
+            .iter_mut()
+            .skip(self.current_hand)
+            .for_each(|hand| hand.set_state(HandState::HandPlayed));
+        self.current_hand = self.hands.len();
+    }
+
+    // returns everything wagered beyond the original bet, i.e. doubles and
+    // splits, when only the original bet is lost to a dealer blackjack
+    pub fn refund_additional_bets(&mut self) -> u32 {
+        let mut refund = 0;
+
+        // every hand that wasn't split off keeps its original bet, e.g. both
+        // hands of a blackjack switch player
+        for hand in self.hands.iter_mut() {
+            let wager = hand.get_wagered_value();
+            let additional = if hand.is_split_stake() {
+                wager
+            } else {
+                hand.get_double_down_wager()
+            };
+
+            hand.get_mut_wager().set_wager(wager - additional);
+            refund += additional;
+        }
+
+        if refund > 0 {
+            self.bankroll.add_funds(refund).ok();
+        }
+
+        refund
+    }
+
+    // doubles the wager on the current hand, deals it one card and ends it
+    pub fn double_down(
+        &mut self,
+        shoe: &mut Shoe,
+        rules: &TableRules,
+    ) -> Result<Arc<Card>, ActionError> {
+        let hand = self.get_current_hand().ok_or(ActionError::NoCurrentHand)?;
+        let wager = hand.get_total_wagered_value();
+        let free = hand.can_free_double_down(rules);
+
+        self.double_down_for(wager, free, shoe, rules)
+    }
+
+    // doubles down for less than the original wager
+    pub fn double_for_less(
+        &mut self,
+        wager: u32,
+        shoe: &mut Shoe,
+        rules: &TableRules,
+    ) -> Result<Arc<Card>, ActionError> {
+        self.double_down_for(wager, false, shoe, rules)
+    }
+
+    // a free double is staked by the house instead of the bankroll
+    fn double_down_for(
+        &mut self,
+        wager: u32,
+        free: bool,
+        shoe: &mut Shoe,
+        rules: &TableRules,
+    ) -> Result<Arc<Card>, ActionError> {
+        let hand = self
+            .hands
+            .get_mut(self.current_hand)
+            .ok_or(ActionError::NoCurrentHand)?;
+
+        if !hand.can_double_down(rules)
+            || wager == 0
+            || wager > hand.get_total_wagered_value()
+        {
+            return Err(ActionError::NotAllowed);
+        }
+
+        if shoe.get_remaining_card_count() == 0 {
+            return Err(ActionError::TakingCardFromShoe);
+        }
+
+        if !free {
+            self.bankroll
+                .sub_funds(wager)
+                .map_err(|_| ActionError::InsufficientFunds)?;
+        }
+
+        let card = shoe.take_card().ok_or(ActionError::TakingCardFromShoe)?;
+
+        if free {
+            hand.free_double_down(wager);
+        } else {
+            hand.double_down(wager);
+        }
+        hand.add_card(card.clone());
+
+        // the hand stays open so the player can choose to rescue it
+        if !hand.can_rescue(rules) {
+            self.stand()?;
+        }
+
+        Ok(card)
+    }
+
+    // takes back the double on the current hand and forfeits the original
+    // wager, returns the amount refunded to the bankroll
+    pub fn rescue(&mut self, rules: &TableRules) -> Result<u32, ActionError> {
+        let hand = self
+            .hands
+            .get_mut(self.current_hand)
+            .ok_or(ActionError::NoCurrentHand)?;
+
+        if !hand.can_rescue(rules) {
+            return Err(ActionError::NotAllowed);
+        }
+
+        let refund = hand.get_double_down_wager();
+
+        self.bankroll.add_funds(refund).map_err(|_| ActionError::NotAllowed)?;
+
+        hand.set_surrendered();
+        hand.set_early_payout(refund);
+        self.stand()?;
+
+        Ok(refund)
+    }
+
+    // forfeits half of the wager on the current hand and ends it, the other
+    // half is returned to the bankroll
+    pub fn surrender(
+        &mut self,
+        rules: &TableRules,
+        dealer_checked: bool,
+    ) -> Result<u32, ActionError> {
+        let hand = self
+            .hands
+            .get_mut(self.current_hand)
+            .ok_or(ActionError::NoCurrentHand)?;
+
+        if !hand.can_surrender(rules, dealer_checked) {
+            return Err(ActionError::NotAllowed);
+        }
+
+        let refund = hand.get_wagered_value() / 2;
+
+        self.bankroll.add_funds(refund).map_err(|_| ActionError::NotAllowed)?;
+
+        hand.set_surrendered();
+        hand.set_early_payout(refund);
+        self.stand()?;
+
+        Ok(refund)
+    }
+
+    // side bets are placed before the cards are dealt
+    pub fn place_side_bet(
+        &mut self,
+        bet: Box<dyn SideBet>,
+        wager: u32,
+    ) -> Result<(), ActionError> {
+        let hand = self
+            .hands
+            .get_mut(self.current_hand)
+            .ok_or(ActionError::NoCurrentHand)?;
+
+        if hand.get_card_count() > 0 || wager == 0 {
+            return Err(ActionError::NotAllowed);
+        }
+
+        self.bankroll
+            .sub_funds(wager)
+            .map_err(|_| ActionError::InsufficientFunds)?;
+
+        hand.add_side_bet(SideBetStake::new(bet, wager));
+
+        Ok(())
+    }
+
+    // insurance is a side bet of up to half the wager on the current hand
+    pub fn place_insurance(&mut self, wager: u32) -> Result<(), ActionError> {
+        let hand = self
+            .hands
+            .get_mut(self.current_hand)
+            .ok_or(ActionError::NoCurrentHand)?;
+
+        if !hand.can_insure() || wager == 0 {
+            return Err(ActionError::NotAllowed);
+        }
+
+        if wager > hand.get_wagered_value() / 2 {
+            return Err(ActionError::NotAllowed);
+        }
+
+        self.bankroll
+            .sub_funds(wager)
+            .map_err(|_| ActionError::InsufficientFunds)?;
+
+        hand.place_insurance(wager);
+
+        Ok(())
+    }
+
+    // a natural can be paid 1:1 right away instead of risking a push against
+    // the dealer, returns the amount paid back to the bankroll
+    pub fn take_even_money(&mut self) -> Result<u32, ActionError> {
+        let hand = self
+            .hands
+            .get_mut(self.current_hand)
+            .ok_or(ActionError::NoCurrentHand)?;
+
+        if !hand.can_insure() || !hand.is_blackjack() {
+            return Err(ActionError::NotAllowed);
+        }
+
+        let payout = hand.get_wagered_value() * 2;
+
+        self.bankroll.add_funds(payout).map_err(|_| ActionError::NotAllowed)?;
+
+        hand.set_even_money();
+        hand.set_won();
+        hand.set_early_payout(payout);
+        self.stand()?;
+
+        Ok(payout)
+    }
+
+    // insurance pays 2:1 when the dealer has blackjack and is lost otherwise,
+    // returns the amount paid back to the bankroll
+    pub fn settle_insurance(&mut self, dealer_natural: bool) -> u32 {
+        let mut payout = 0;
+
+        for hand in self.hands.iter_mut() {
+            if dealer_natural {
+                payout += hand.get_insurance_value() * 3;
+            }
+            hand.reset_insurance();
+        }
+
+        if payout > 0 {
+            self.bankroll.add_funds(payout).ok();
+        }
+
+        payout
+    }
+
+    // settles every hand against the dealer's and pays the bankroll
+    pub fn settle(
+        &mut self,
+        dealer: &BlackjackHand,
+        rules: &TableRules,
+    ) -> Vec<Settlement> {
+        let mut settlements = vec![];
+
+        for (hand_index, hand) in self.hands.iter_mut().enumerate() {
+            let side_bet_wagered = hand.get_side_bet_wagered_value();
+            let side_bet_paid = hand.settle_side_bets(dealer.get_cards());
+
+            // hands resolved during play were already paid, they are only
+            // reported
+            let (result, wagered, paid, payable) =
+                match settle_hand(hand, dealer, rules) {
+                    Some((result, paid)) => {
+                        (result, hand.get_wagered_value(), paid, paid)
+                    }
+                    None => match hand.take_early_payout() {
+                        Some(paid) => (
+                            hand.get_result_state().clone(),
+                            hand.get_wagered_value(),
+                            paid,
+                            0,
+                        ),
+                        None if side_bet_wagered > 0 => {
+                            (hand.get_result_state().clone(), 0, 0, 0)
+                        }
+                        None => continue,
+                    },
+                };
+
+            self.bankroll.add_funds(payable + side_bet_paid).ok();
+            settlements.push(Settlement {
+                player_id: self.id,
+                hand_index,
+                result,
+                wagered,
+                paid,
+                side_bet_wagered,
+                side_bet_paid,
+            });
+        }
+
+        settlements
+    }
+
+    // the bankroll has to cover the new hand unless the house stakes it
+    pub fn can_split(&self, rules: &TableRules) -> bool {
+        match self.get_current_hand() {
+            Some(hand) => {
+                hand.can_split(rules)
+                    && self.splits < rules.max_splits
+                    && (hand.can_free_split(rules)
+                        || self.bankroll.get_bankroll()
+                            >= hand.get_total_wagered_value())
+            }
+            None => false,
+        }
+    }
+
+    // splits the current hand into two hands with the same wager, the second
+    // hand is placed right after the current hand and both are dealt a card
+    pub fn split(
+        &mut self,
+        shoe: &mut Shoe,
+        rules: &TableRules,
+    ) -> Result<Vec<Arc<Card>>, ActionError> {
+        let hand = self
+            .hands
+            .get_mut(self.current_hand)
+            .ok_or(ActionError::NoCurrentHand)?;
+
+        if !hand.can_split(rules) || self.splits >= rules.max_splits {
+            return Err(ActionError::NotAllowed);
+        }
+
+        if shoe.get_remaining_card_count() < 2 {
+            return Err(ActionError::TakingCardFromShoe);
+        }
+
+        // the first hand keeps the stake of the hand that was split, the new
+        // hand is staked the same amount by the player or, when free, the house
+        let wager = hand.get_wagered_value();
+        let free_wager = hand.get_free_wagered_value();
+        let stake = hand.get_total_wagered_value();
+        let free = hand.can_free_split(rules);
+
+        if !free {
+            self.bankroll
+                .sub_funds(stake)
+                .map_err(|_| ActionError::InsufficientFunds)?;
+        }
+
+        let mut hands = hand.split(rules).ok_or(ActionError::NotAllowed)?;
+        let mut dealt = Vec::with_capacity(2);
+
+        for hand in hands.iter_mut() {
+            let card =
+                shoe.take_card().ok_or(ActionError::TakingCardFromShoe)?;
+
+            hand.add_card(card.clone());
+            dealt.push(card);
+        }
+
+        let [mut first, mut second] = hands;
+
+        first.set_wagered_value(wager);
+        first.add_free_wager_to_wagered_value(free_wager);
+
+        if free {
+            second.set_wagered_value(0);
+            second.add_free_wager_to_wagered_value(stake);
+        } else {
+            second.set_wagered_value(stake);
+        }
+
+        self.hands[self.current_hand] = first;
+        self.hands.insert(self.current_hand + 1, second);
+        self.splits += 1;
+
+        Ok(dealt)
+    }
+
     pub fn push_card_to_current_hand(
         &mut self,
         card: Arc<Card>,
@@ -68,6 +651,40 @@ impl Player {
         Err(format_err!("Could not add card"))
     }
 
+    pub fn push_card_face_down_to_current_hand(
+        &mut self,
+        card: Arc<Card>,
+    ) -> Result<(), Error> {
+        if let Some(hand) = self.hands.get_mut(self.current_hand) {
+            hand.add_card_face_down(card);
+            return Ok(());
+        }
+        Err(format_err!("Could not add card"))
+    }
+
+    // turns over every face down card, returns the revealed cards
+    pub fn reveal_cards(&mut self) -> Vec<Arc<Card>> {
+        self.hands.iter_mut().flat_map(|hand| hand.reveal_cards()).collect()
+    }
+
+    pub fn push_card_to_hand(
+        &mut self,
+        index: usize,
+        card: Arc<Card>,
+    ) -> Result<(), Error> {
+        if let Some(hand) = self.hands.get_mut(index) {
+            hand.add_card(card);
+            return Ok(());
+        }
+        Err(format_err!("Could not add card"))
+    }
+
+    pub fn reset_hands(&mut self) {
+        self.hands = vec![BlackjackHand::new(self.player_type.clone())];
+        self.current_hand = 0;
+        self.splits = 0;
+    }
+
     pub fn get_available_funds(&self) -> u32 {
         self.bankroll.get_bankroll()
     }
@@ -83,12 +700,539 @@ impl Player {
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
+    use crate::blackjack::blackjack_hand::ResultsState;
+    use crate::blackjack::hand_value::{HandValue, WithHandValue};
+    use crate::blackjack::side_bet::lucky_ladies::LuckyLadies;
+    use crate::blackjack::side_bet::perfect_pairs::PerfectPairs;
+    use crate::blackjack::table_rules::{DoubleDown, HoleCard, Surrender};
     use crate::cards::card::Card;
+    use crate::cards::parse::parse_cards;
     use crate::cards::rank::Rank;
     use crate::cards::suit::Suit;
 
     use super::*;
 
+    fn player_with_hand(shoe: &mut Shoe, wager: u32) -> Player {
+        let mut player = Player::new(String::from("Test"));
+        player.add_funds(100);
+        player.place_wager(wager).unwrap();
+
+        for _ in 0..2 {
+            let card = shoe.take_card().unwrap();
+            player.push_card_to_current_hand(card).unwrap();
+        }
+
+        player
+    }
+
+    fn dealer_hand(cards: &str) -> BlackjackHand {
+        let cards = parse_cards(cards).unwrap().into_iter().map(Arc::new);
+        BlackjackHand::with_cards(PlayerType::Dealer, cards.collect())
+    }
+
+    #[test]
+    fn it_places_wager_from_bankroll() {
+        let mut player = Player::new(String::from("Test"));
+        player.add_funds(100);
+
+        assert!(player.place_wager(40).is_ok());
+        assert_eq!(player.get_available_funds(), 60);
+        assert_eq!(player.get_current_hand().unwrap().get_wagered_value(), 40);
+
+        assert_eq!(player.place_wager(80), Err(ActionError::InsufficientFunds));
+        assert_eq!(player.get_current_hand().unwrap().get_wagered_value(), 40);
+    }
+
+    #[test]
+    fn it_splits_pair_into_two_hands() {
+        let mut shoe = Shoe::from_str("8S 8H 3C 2D").unwrap();
+        let rules = TableRules::default();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert!(player.can_split(&rules));
+
+        let dealt = player.split(&mut shoe, &rules).unwrap();
+
+        assert_eq!(dealt.len(), 2);
+        assert_eq!(player.get_hands().len(), 2);
+        assert_eq!(player.get_available_funds(), 80);
+
+        let hands = player.get_hands();
+        assert_eq!(hands[0].get_value(), Some(HandValue::V(11)));
+        assert_eq!(hands[1].get_value(), Some(HandValue::V(10)));
+        assert_eq!(hands[0].get_wagered_value(), 10);
+        assert_eq!(hands[1].get_wagered_value(), 10);
+
+        assert!(player.stand().is_ok());
+        assert_eq!(player.get_current_hand_index(), 1);
+        assert!(player.stand().is_ok());
+        assert!(player.is_done());
+    }
+
+    #[test]
+    fn it_limits_resplits() {
+        let mut shoe = Shoe::from_str("8S 8H 8C 8D 8S 2D 3D").unwrap();
+        let rules = TableRules {
+            max_splits: 1,
+            ..Default::default()
+        };
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert!(player.split(&mut shoe, &rules).is_ok());
+        assert_eq!(player.get_current_hand().unwrap().get_card_count(), 2);
+        assert!(player.get_current_hand().unwrap().can_split(&rules));
+
+        assert!(!player.can_split(&rules));
+        assert_eq!(
+            player.split(&mut shoe, &rules),
+            Err(ActionError::NotAllowed)
+        );
+    }
+
+    #[test]
+    fn it_does_not_split_without_funds() {
+        let mut shoe = Shoe::from_str("8S 8H 3C 2D").unwrap();
+        let rules = TableRules::default();
+        let mut player = player_with_hand(&mut shoe, 100);
+
+        assert!(!player.can_split(&rules));
+        assert_eq!(
+            player.split(&mut shoe, &rules),
+            Err(ActionError::InsufficientFunds)
+        );
+        assert_eq!(player.get_hands().len(), 1);
+    }
+
+    #[test]
+    fn it_doubles_down() {
+        let mut shoe = Shoe::from_str("5S 6H KC 2D").unwrap();
+        let rules = TableRules::default();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        let card = player.double_down(&mut shoe, &rules).unwrap();
+        assert_eq!(card.rank, Rank::King);
+
+        let hand = player.get_hands().first().unwrap();
+        assert_eq!(hand.get_wagered_value(), 20);
+        assert_eq!(hand.get_double_down_wager(), 10);
+        assert_eq!(hand.get_value(), Some(HandValue::V(21)));
+        assert_eq!(player.get_available_funds(), 80);
+        assert!(player.is_done());
+        assert_eq!(
+            player.hit(&mut shoe, &rules),
+            Err(ActionError::NoCurrentHand)
+        );
+    }
+
+    #[test]
+    fn it_doubles_for_less() {
+        let mut shoe = Shoe::from_str("5S 6H KC").unwrap();
+        let rules = TableRules::default();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert_eq!(
+            player.double_for_less(11, &mut shoe, &rules),
+            Err(ActionError::NotAllowed)
+        );
+        assert!(player.double_for_less(4, &mut shoe, &rules).is_ok());
+
+        let hand = player.get_hands().first().unwrap();
+        assert_eq!(hand.get_wagered_value(), 14);
+        assert_eq!(player.get_available_funds(), 86);
+    }
+
+    #[test]
+    fn it_does_not_double_down_without_funds() {
+        let mut shoe = Shoe::from_str("5S 6H KC").unwrap();
+        let rules = TableRules::default();
+        let mut player = player_with_hand(&mut shoe, 60);
+
+        assert_eq!(
+            player.double_down(&mut shoe, &rules),
+            Err(ActionError::InsufficientFunds)
+        );
+
+        let hand = player.get_current_hand().unwrap();
+        assert_eq!(hand.get_wagered_value(), 60);
+        assert_eq!(hand.get_card_count(), 2);
+        assert_eq!(shoe.get_remaining_card_count(), 1);
+    }
+
+    #[test]
+    fn it_does_not_double_down_against_house_rules() {
+        let mut shoe = Shoe::from_str("5S 3H KC").unwrap();
+        let rules = TableRules {
+            double_down: DoubleDown::NineToEleven,
+            ..Default::default()
+        };
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert_eq!(
+            player.double_down(&mut shoe, &rules),
+            Err(ActionError::NotAllowed)
+        );
+        assert_eq!(player.get_available_funds(), 90);
+    }
+
+    #[test]
+    fn it_does_not_double_down_split_aces() {
+        let mut shoe = Shoe::from_str("AS AH 5C 6D 9C").unwrap();
+        let rules = TableRules::default();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        player.split(&mut shoe, &rules).unwrap();
+
+        assert_eq!(
+            player.double_down(&mut shoe, &rules),
+            Err(ActionError::NotAllowed)
+        );
+        assert_eq!(
+            player.double_for_less(5, &mut shoe, &rules),
+            Err(ActionError::NotAllowed)
+        );
+
+        let hand = player.get_current_hand().unwrap();
+        assert_eq!(hand.get_wagered_value(), 10);
+        assert!(!hand.is_doubled());
+        assert_eq!(player.get_available_funds(), 80);
+        assert_eq!(shoe.get_remaining_card_count(), 1);
+    }
+
+    #[test]
+    fn it_surrenders_half_of_wager() {
+        let mut shoe = Shoe::from_str("10S 6H").unwrap();
+        let rules = TableRules {
+            surrender: Surrender::Late,
+            ..Default::default()
+        };
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert_eq!(
+            player.surrender(&rules, false),
+            Err(ActionError::NotAllowed)
+        );
+        assert_eq!(player.surrender(&rules, true), Ok(5));
+
+        let hand = player.get_hands().first().unwrap();
+        assert_eq!(hand.get_result_state(), &ResultsState::Surrendered);
+        assert_eq!(player.get_available_funds(), 95);
+        assert!(player.is_done());
+
+        let settlements = player.settle(&dealer_hand("10C 9H"), &rules);
+        assert_eq!(settlements.len(), 1);
+        assert_eq!(settlements[0].result, ResultsState::Surrendered);
+        assert_eq!(settlements[0].wagered, 10);
+        assert_eq!(settlements[0].get_net(), -5);
+        assert_eq!(player.get_available_funds(), 95);
+    }
+
+    #[test]
+    fn it_pays_insurance_two_to_one() {
+        let mut shoe = Shoe::from_str("10S 6H").unwrap();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert_eq!(player.place_insurance(6), Err(ActionError::NotAllowed));
+        assert!(player.place_insurance(5).is_ok());
+        assert_eq!(player.place_insurance(5), Err(ActionError::NotAllowed));
+        assert_eq!(player.get_available_funds(), 85);
+
+        assert_eq!(player.settle_insurance(true), 15);
+        assert_eq!(player.get_available_funds(), 100);
+        assert_eq!(player.settle_insurance(true), 0);
+    }
+
+    #[test]
+    fn it_loses_insurance_without_dealer_blackjack() {
+        let mut shoe = Shoe::from_str("10S 6H").unwrap();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert!(player.place_insurance(5).is_ok());
+        assert_eq!(player.settle_insurance(false), 0);
+        assert_eq!(player.get_available_funds(), 85);
+    }
+
+    #[test]
+    fn it_pays_even_money_on_natural() {
+        let mut shoe = Shoe::from_str("10S 6H").unwrap();
+        let mut player = player_with_hand(&mut shoe, 10);
+        assert_eq!(player.take_even_money(), Err(ActionError::NotAllowed));
+
+        let mut shoe = Shoe::from_str("AS KH").unwrap();
+        let mut player = player_with_hand(&mut shoe, 10);
+        assert_eq!(player.take_even_money(), Ok(20));
+
+        let hand = player.get_hands().first().unwrap();
+        assert!(hand.is_even_money());
+        assert_eq!(hand.get_result_state(), &ResultsState::Won);
+        assert_eq!(player.get_available_funds(), 110);
+        assert!(player.is_done());
+
+        let rules = TableRules::default();
+        let settlements = player.settle(&dealer_hand("AC KD"), &rules);
+        assert_eq!(settlements[0].get_net(), 10);
+        assert_eq!(player.get_available_funds(), 110);
+        assert!(player.settle(&dealer_hand("AC KD"), &rules).is_empty());
+    }
+
+    #[test]
+    fn it_refunds_doubles_and_splits() {
+        let mut shoe = Shoe::from_str("8S 8H 3C 2D 9S").unwrap();
+        let rules = TableRules::default();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert!(player.split(&mut shoe, &rules).is_ok());
+        assert!(player.double_down(&mut shoe, &rules).is_ok());
+        assert_eq!(player.get_available_funds(), 70);
+
+        player.end_turn();
+        assert!(player.is_done());
+
+        assert_eq!(player.refund_additional_bets(), 20);
+        assert_eq!(player.get_available_funds(), 90);
+        assert_eq!(player.get_hands()[0].get_wagered_value(), 10);
+        assert_eq!(player.get_hands()[1].get_wagered_value(), 0);
+    }
+
+    #[test]
+    fn it_rescues_double_down() {
+        let mut shoe = Shoe::from_str("5S 6H 2D 3C").unwrap();
+        let rules = TableRules::spanish_21();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert_eq!(player.rescue(&rules), Err(ActionError::NotAllowed));
+        assert!(player.double_down(&mut shoe, &rules).is_ok());
+        assert!(!player.is_done());
+        assert_eq!(player.hit(&mut shoe, &rules), Err(ActionError::NotAllowed));
+
+        assert_eq!(player.rescue(&rules), Ok(10));
+        assert_eq!(player.get_available_funds(), 90);
+        assert!(player.is_done());
+
+        let hand = player.get_hands().first().unwrap();
+        assert_eq!(hand.get_result_state(), &ResultsState::Surrendered);
+
+        let settlements = player.settle(&dealer_hand("10C 7H"), &rules);
+        assert_eq!(settlements[0].wagered, 20);
+        assert_eq!(settlements[0].get_net(), -10);
+    }
+
+    #[test]
+    fn it_switches_second_cards() {
+        let rules = TableRules::blackjack_switch();
+        let mut player = Player::new(String::from("Tester"));
+        player.add_funds(100);
+
+        assert_eq!(
+            player.place_switch_wagers(10, &TableRules::default()),
+            Err(ActionError::NotAllowed)
+        );
+        assert!(player.place_switch_wagers(10, &rules).is_ok());
+        assert_eq!(player.get_available_funds(), 80);
+
+        for card in parse_cards("AS 5C 6H KD").unwrap() {
+            let index = if player.get_hands()[0].get_card_count() < 2 {
+                0
+            } else {
+                1
+            };
+            player.push_card_to_hand(index, Arc::new(card)).unwrap();
+        }
+
+        assert!(player.switch().is_ok());
+        assert_eq!(player.switch(), Err(ActionError::NotAllowed));
+
+        // the switched ace and king is a plain 21, not a natural
+        let hands = player.get_hands();
+        assert!(!hands[0].is_blackjack());
+        assert_eq!(hands[0].best_total(), Some(21));
+        assert_eq!(hands[1].get_value(), Some(HandValue::V(11)));
+        assert_eq!(hands[1].get_wagered_value(), 10);
+    }
+
+    #[test]
+    fn it_refunds_doubles_on_both_switch_hands() {
+        let mut shoe = Shoe::from_str("5S 6C 4H 5D 9S 8H").unwrap();
+        let rules = TableRules {
+            hole_card: HoleCard::OriginalBetsOnly,
+            ..TableRules::blackjack_switch()
+        };
+        let mut player = Player::new(String::from("Tester"));
+        player.add_funds(100);
+
+        assert_eq!(
+            player.place_switch_wagers(u32::max_value(), &rules),
+            Err(ActionError::InsufficientFunds)
+        );
+        assert!(player.place_switch_wagers(10, &rules).is_ok());
+
+        for index in &[0, 1, 0, 1] {
+            let card = shoe.take_card().unwrap();
+            player.push_card_to_hand(*index, card).unwrap();
+        }
+
+        assert!(player.double_down(&mut shoe, &rules).is_ok());
+        assert!(player.double_down(&mut shoe, &rules).is_ok());
+        assert_eq!(player.get_available_funds(), 60);
+
+        assert_eq!(player.refund_additional_bets(), 20);
+        assert_eq!(player.get_available_funds(), 80);
+        assert_eq!(player.get_hands()[0].get_wagered_value(), 10);
+        assert_eq!(player.get_hands()[1].get_wagered_value(), 10);
+    }
+
+    #[test]
+    fn it_splits_and_doubles_for_free() {
+        let mut shoe = Shoe::from_str("5S 5H 4S 6C KD KH").unwrap();
+        let rules = TableRules::free_bet();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        // splitting the fives leaves a hard 9 and 11 which are both free doubles
+        assert!(player.split(&mut shoe, &rules).is_ok());
+        assert_eq!(player.get_available_funds(), 90);
+
+        let hands = player.get_hands();
+        assert_eq!(hands[0].get_wagered_value(), 10);
+        assert_eq!(hands[1].get_wagered_value(), 0);
+        assert_eq!(hands[1].get_free_wagered_value(), 10);
+
+        assert!(player.double_down(&mut shoe, &rules).is_ok());
+        assert!(player.double_down(&mut shoe, &rules).is_ok());
+        assert_eq!(player.get_available_funds(), 90);
+
+        let hands = player.get_hands();
+        assert_eq!(hands[0].get_total_wagered_value(), 20);
+        assert_eq!(hands[1].get_free_wagered_value(), 20);
+    }
+
+    #[test]
+    fn it_splits_for_free_without_funds() {
+        let mut shoe = Shoe::from_str("8S 8H 3C 2D").unwrap();
+        let mut player = player_with_hand(&mut shoe, 100);
+
+        assert!(!player.can_split(&TableRules::default()));
+        assert!(player.can_split(&TableRules::free_bet()));
+        assert!(player.split(&mut shoe, &TableRules::free_bet()).is_ok());
+    }
+
+    #[test]
+    fn it_pays_to_split_tens_in_free_bet() {
+        let mut shoe = Shoe::from_str("KS QH 8C 8D").unwrap();
+        let rules = TableRules::free_bet();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert!(player.split(&mut shoe, &rules).is_ok());
+        assert_eq!(player.get_available_funds(), 80);
+        assert_eq!(player.get_hands()[1].get_free_wagered_value(), 0);
+    }
+
+    #[test]
+    fn it_buys_twists_and_sticks() {
+        let mut shoe = Shoe::from_str("2S 4H 3C 5D 2D").unwrap();
+        let rules = TableRules::pontoon();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert_eq!(
+            player.buy(20, &mut shoe, &rules),
+            Err(ActionError::NotAllowed)
+        );
+        assert!(player.buy(10, &mut shoe, &rules).is_ok());
+        assert_eq!(player.get_available_funds(), 80);
+        assert_eq!(player.stick(&rules), Err(ActionError::NotAllowed));
+
+        assert!(player.twist(&mut shoe, &rules).is_ok());
+        assert_eq!(
+            player.buy(10, &mut shoe, &rules),
+            Err(ActionError::NotAllowed)
+        );
+        assert_eq!(player.stick(&rules), Err(ActionError::NotAllowed));
+
+        // 2-4-3-5-2 is a five card trick, no more cards can be taken
+        assert!(player.twist(&mut shoe, &rules).is_ok());
+        assert!(player.stick(&rules).is_ok());
+        assert!(player.is_done());
+        assert_eq!(player.get_hands()[0].get_wagered_value(), 20);
+    }
+
+    #[test]
+    fn it_does_not_buy_from_empty_shoe() {
+        let mut shoe = Shoe::from_str("2S 4H").unwrap();
+        let rules = TableRules::pontoon();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert_eq!(
+            player.buy(10, &mut shoe, &rules),
+            Err(ActionError::TakingCardFromShoe)
+        );
+        assert_eq!(player.get_available_funds(), 90);
+        assert_eq!(player.get_hands()[0].get_wagered_value(), 10);
+    }
+
+    #[test]
+    fn it_settles_side_bets_with_hand() {
+        let mut player = Player::new(String::from("Tester"));
+        player.add_funds(100);
+        player.place_wager(10).unwrap();
+
+        assert!(player
+            .place_side_bet(Box::new(PerfectPairs::new()), 5)
+            .is_ok());
+        assert!(player.place_side_bet(Box::new(LuckyLadies::new()), 5).is_ok());
+        assert_eq!(player.get_available_funds(), 80);
+
+        let mut shoe = Shoe::from_str("8S 8H 3C 10D 9S").unwrap();
+        let rules = TableRules::default();
+        for _ in 0..2 {
+            let card = shoe.take_card().unwrap();
+            player.push_card_to_current_hand(card).unwrap();
+        }
+        assert_eq!(
+            player.place_side_bet(Box::new(PerfectPairs::new()), 5),
+            Err(ActionError::NotAllowed)
+        );
+
+        // the pair is kept for the side bets once the eights are split
+        assert!(player.split(&mut shoe, &rules).is_ok());
+        player.end_turn();
+
+        let settlements = player.settle(&dealer_hand("10C 9H"), &rules);
+
+        // 8-3 loses, 8-10 loses, the mixed pair pays 6:1
+        assert_eq!(settlements[0].side_bet_wagered, 10);
+        assert_eq!(settlements[0].side_bet_paid, 35);
+        assert_eq!(settlements[1].side_bet_wagered, 0);
+        assert_eq!(player.get_available_funds(), 105);
+    }
+
+    #[test]
+    fn split_aces_can_not_hit() {
+        let mut shoe = Shoe::from_str("AS AH 9C KD 5C").unwrap();
+        let rules = TableRules::default();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert!(player.split(&mut shoe, &rules).is_ok());
+        assert_eq!(player.hit(&mut shoe, &rules), Err(ActionError::NotAllowed));
+
+        let hands = player.get_hands();
+        assert_eq!(hands[1].get_value(), Some(HandValue::Ace(11, 21)));
+        assert!(!hands[1].is_blackjack());
+    }
+
+    #[test]
+    fn split_aces_can_not_double_down() {
+        let mut shoe = Shoe::from_str("AS AH 5C 6D 9C").unwrap();
+        let rules = TableRules::default();
+        let mut player = player_with_hand(&mut shoe, 10);
+
+        assert!(player.split(&mut shoe, &rules).is_ok());
+        assert_eq!(
+            player.double_down(&mut shoe, &rules),
+            Err(ActionError::NotAllowed)
+        );
+        assert_eq!(player.get_hands()[0].get_card_count(), 2);
+    }
+
     #[test]
     fn it_does_push_cards_to_curr_hand() {
         let mut player = Player::new(String::from("Test"));
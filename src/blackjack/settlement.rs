@@ -0,0 +1,372 @@
+use uuid::Uuid;
+
+use crate::blackjack::blackjack_hand::{
+    BlackjackHand, ResultState, ResultsState,
+};
+use crate::blackjack::hand_value::{HandTotal, WithHandValue};
+use crate::blackjack::spanish_21;
+use crate::blackjack::table_rules::TableRules;
+use crate::blackjack::wager::WithWager;
+
+// the outcome of a single hand once the round is over
+#[derive(Clone, PartialEq, Debug)]
+pub struct Settlement {
+    pub player_id: Uuid,
+    pub hand_index: usize,
+    pub result: ResultsState,
+    pub wagered: u32,
+    // everything paid back to the bankroll, including the returned wager
+    pub paid: u32,
+    pub side_bet_wagered: u32,
+    pub side_bet_paid: u32,
+}
+
+impl Settlement {
+    // the amount won, or lost when negative, including the side bets
+    pub fn get_net(&self) -> i64 {
+        i64::from(self.paid) + i64::from(self.side_bet_paid)
+            - i64::from(self.wagered)
+            - i64::from(self.side_bet_wagered)
+    }
+}
+
+// compares the hand with the dealer's, returns the result and the amount to
+// pay back to the bankroll. hands that were already resolved during play,
+// e.g. surrendered or paid even money, are not settled again
+pub fn resolve_hand(
+    hand: &BlackjackHand,
+    dealer: &BlackjackHand,
+    rules: &TableRules,
+) -> Option<(ResultsState, u32)> {
+    if hand.get_result_state() != &ResultsState::Default {
+        return None;
+    }
+
+    // the free part of the stake is paid when it wins but never returned
+    let wager = hand.get_wagered_value();
+    let free_wager = hand.get_free_wagered_value();
+    let player_natural = hand.is_blackjack();
+    let dealer_natural = dealer.is_blackjack();
+    let dealer_pushes = rules.dealer_22_pushes
+        && dealer.get_total() == Some(HandTotal::Bust(22));
+    let always_wins =
+        rules.player_21_always_wins && hand.best_total() == Some(21);
+    let charlie = hand.is_charlie(rules);
+
+    let result = match (player_natural, dealer_natural) {
+        (true, true) if rules.player_21_always_wins => ResultsState::Natural,
+        (true, true) if rules.dealer_wins_ties => {
+            if rules.player_natural_wins_ties {
+                ResultsState::Natural
+            } else {
+                ResultsState::Lost
+            }
+        }
+        (true, true) => ResultsState::Pushed,
+        (true, false) => ResultsState::Natural,
+        (false, _) if always_wins => ResultsState::Won,
+        (false, false) if charlie => ResultsState::Won,
+        (false, true) => ResultsState::Lost,
+        (false, false) => match (hand.best_total(), dealer.best_total()) {
+            (None, _) => ResultsState::Busted,
+            (_, None) if dealer_pushes => ResultsState::Pushed,
+            (_, None) => ResultsState::Won,
+            (Some(player), Some(dealer)) if player > dealer => {
+                ResultsState::Won
+            }
+            (Some(player), Some(dealer))
+                if player == dealer && !rules.dealer_wins_ties =>
+            {
+                ResultsState::Pushed
+            }
+            _ => ResultsState::Lost,
+        },
+    };
+
+    let bonus = match &rules.charlie {
+        Some(rule) if charlie => rule.bonus,
+        _ if rules.bonus_21_payouts => spanish_21::bonus_payout(hand),
+        _ => None,
+    };
+
+    let paid = match (result.clone(), bonus) {
+        (ResultsState::Natural, _) => {
+            wager + rules.blackjack_payout.winnings(wager)
+        }
+        (ResultsState::Won, Some(bonus)) => {
+            wager + bonus.winnings(wager) + free_wager
+        }
+        (ResultsState::Won, None) => wager * 2 + free_wager,
+        (ResultsState::Pushed, _) => wager,
+        _ => 0,
+    };
+
+    Some((result, paid))
+}
+
+// sets the result on the hand and returns the amount to pay
+pub fn settle_hand(
+    hand: &mut BlackjackHand,
+    dealer: &BlackjackHand,
+    rules: &TableRules,
+) -> Option<(ResultsState, u32)> {
+    let (result, paid) = resolve_hand(hand, dealer, rules)?;
+    hand.set_result_state(result.clone());
+    Some((result, paid))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::blackjack::player::PlayerType;
+    use crate::blackjack::table_rules::{Charlie, Payout};
+    use crate::cards::{Card, Rank, Suit};
+
+    use super::*;
+
+    fn hand(player_type: PlayerType, ranks: &[Rank]) -> BlackjackHand {
+        let cards = ranks
+            .iter()
+            .map(|rank| Arc::new(Card::from(Suit::Spade, *rank)))
+            .collect();
+        let mut hand = BlackjackHand::with_cards(player_type, cards);
+        hand.set_wagered_value(10);
+        hand
+    }
+
+    fn resolve(player: &[Rank], dealer: &[Rank]) -> (ResultsState, u32) {
+        let player = hand(PlayerType::Player, player);
+        let dealer = hand(PlayerType::Dealer, dealer);
+        resolve_hand(&player, &dealer, &TableRules::default()).unwrap()
+    }
+
+    #[test]
+    fn it_pays_natural() {
+        let natural = [Rank::Ace, Rank::King];
+
+        assert_eq!(
+            resolve(&natural, &[Rank::Ten, Rank::Nine]),
+            (ResultsState::Natural, 25)
+        );
+        assert_eq!(resolve(&natural, &natural), (ResultsState::Pushed, 10));
+        assert_eq!(
+            resolve(&[Rank::Seven, Rank::Four, Rank::Ten], &natural),
+            (ResultsState::Lost, 0)
+        );
+    }
+
+    #[test]
+    fn it_pays_natural_at_table_payout() {
+        let player = hand(PlayerType::Player, &[Rank::Ace, Rank::Queen]);
+        let dealer = hand(PlayerType::Dealer, &[Rank::Ten, Rank::Seven]);
+        let rules = TableRules {
+            blackjack_payout: Payout::six_to_five(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            resolve_hand(&player, &dealer, &rules),
+            Some((ResultsState::Natural, 22))
+        );
+    }
+
+    #[test]
+    fn it_compares_totals() {
+        let dealer = [Rank::Ten, Rank::Eight];
+
+        assert_eq!(
+            resolve(&[Rank::Ten, Rank::Nine], &dealer),
+            (ResultsState::Won, 20)
+        );
+        assert_eq!(
+            resolve(&[Rank::Ace, Rank::Seven], &dealer),
+            (ResultsState::Pushed, 10)
+        );
+        assert_eq!(
+            resolve(&[Rank::Ten, Rank::Seven], &dealer),
+            (ResultsState::Lost, 0)
+        );
+    }
+
+    #[test]
+    fn it_settles_busts() {
+        let busted = [Rank::Ten, Rank::Six, Rank::King];
+
+        assert_eq!(
+            resolve(&busted, &[Rank::Ten, Rank::Eight]),
+            (ResultsState::Busted, 0)
+        );
+        assert_eq!(resolve(&busted, &busted), (ResultsState::Busted, 0));
+        assert_eq!(
+            resolve(&[Rank::Ten, Rank::Two], &busted),
+            (ResultsState::Won, 20)
+        );
+    }
+
+    #[test]
+    fn it_always_pays_player_21_in_spanish_21() {
+        let rules = TableRules::spanish_21();
+        let dealer = hand(PlayerType::Dealer, &[Rank::Ace, Rank::King]);
+
+        let player = hand(PlayerType::Player, &[Rank::Ace, Rank::Queen]);
+        assert_eq!(
+            resolve_hand(&player, &dealer, &rules),
+            Some((ResultsState::Natural, 25))
+        );
+
+        let player =
+            hand(PlayerType::Player, &[Rank::Nine, Rank::Two, Rank::Jack]);
+        let dealer =
+            hand(PlayerType::Dealer, &[Rank::Nine, Rank::Five, Rank::Seven]);
+        assert_eq!(
+            resolve_hand(&player, &dealer, &rules),
+            Some((ResultsState::Won, 20))
+        );
+    }
+
+    #[test]
+    fn it_pays_spanish_21_bonus() {
+        let rules = TableRules::spanish_21();
+        let dealer =
+            hand(PlayerType::Dealer, &[Rank::Nine, Rank::Five, Rank::Seven]);
+        let player = hand(
+            PlayerType::Player,
+            &[Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Seven],
+        );
+
+        assert_eq!(
+            resolve_hand(&player, &dealer, &rules),
+            Some((ResultsState::Won, 25))
+        );
+        assert_eq!(
+            resolve_hand(&player, &dealer, &TableRules::default()),
+            Some((ResultsState::Pushed, 10))
+        );
+    }
+
+    #[test]
+    fn it_pushes_on_dealer_22() {
+        let rules = TableRules::blackjack_switch();
+        let dealer =
+            hand(PlayerType::Dealer, &[Rank::Ten, Rank::Six, Rank::Six]);
+        let player = hand(PlayerType::Player, &[Rank::Ten, Rank::Two]);
+
+        assert_eq!(
+            resolve_hand(&player, &dealer, &rules),
+            Some((ResultsState::Pushed, 10))
+        );
+        assert_eq!(
+            resolve_hand(&player, &dealer, &TableRules::default()),
+            Some((ResultsState::Won, 20))
+        );
+
+        let dealer =
+            hand(PlayerType::Dealer, &[Rank::Ten, Rank::Six, Rank::Seven]);
+        assert_eq!(
+            resolve_hand(&player, &dealer, &rules),
+            Some((ResultsState::Won, 20))
+        );
+    }
+
+    #[test]
+    fn it_pays_winnings_on_free_stake() {
+        let rules = TableRules::free_bet();
+        let dealer = hand(PlayerType::Dealer, &[Rank::Ten, Rank::Eight]);
+
+        let mut player = hand(PlayerType::Player, &[Rank::Five, Rank::Six]);
+        player.free_double_down(10);
+        player.add_card(Arc::new(Card::from(Suit::Club, Rank::Nine)));
+
+        assert_eq!(
+            resolve_hand(&player, &dealer, &rules),
+            Some((ResultsState::Won, 30))
+        );
+
+        let mut player = hand(PlayerType::Player, &[Rank::Five, Rank::Six]);
+        player.free_double_down(10);
+        player.add_card(Arc::new(Card::from(Suit::Club, Rank::Two)));
+
+        assert_eq!(
+            resolve_hand(&player, &dealer, &rules),
+            Some((ResultsState::Lost, 0))
+        );
+    }
+
+    #[test]
+    fn it_lets_dealer_win_ties_in_double_exposure() {
+        let rules = TableRules::double_exposure();
+        let natural = [Rank::Ace, Rank::King];
+
+        let player = hand(PlayerType::Player, &[Rank::Ten, Rank::Eight]);
+        let dealer = hand(PlayerType::Dealer, &[Rank::Ten, Rank::Eight]);
+        assert_eq!(
+            resolve_hand(&player, &dealer, &rules),
+            Some((ResultsState::Lost, 0))
+        );
+
+        let player = hand(PlayerType::Player, &natural);
+        let dealer = hand(PlayerType::Dealer, &natural);
+        assert_eq!(
+            resolve_hand(&player, &dealer, &rules),
+            Some((ResultsState::Natural, 20))
+        );
+
+        let rules = TableRules {
+            player_natural_wins_ties: false,
+            ..rules
+        };
+        assert_eq!(
+            resolve_hand(&player, &dealer, &rules),
+            Some((ResultsState::Lost, 0))
+        );
+    }
+
+    #[test]
+    fn it_pays_charlie() {
+        let charlie =
+            [Rank::Two, Rank::Three, Rank::Two, Rank::Four, Rank::Six];
+        let player = hand(PlayerType::Player, &charlie);
+        let dealer = hand(PlayerType::Dealer, &[Rank::Ten, Rank::Nine]);
+        let natural = hand(PlayerType::Dealer, &[Rank::Ace, Rank::King]);
+
+        let rules = TableRules {
+            charlie: Some(Charlie::new(5)),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_hand(&player, &dealer, &rules),
+            Some((ResultsState::Won, 20))
+        );
+        assert_eq!(
+            resolve_hand(&player, &natural, &rules),
+            Some((ResultsState::Lost, 0))
+        );
+        assert_eq!(
+            resolve_hand(&player, &dealer, &TableRules::default()),
+            Some((ResultsState::Lost, 0))
+        );
+
+        let rules = TableRules {
+            charlie: Some(Charlie::with_bonus(5, Payout::new(2, 1))),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_hand(&player, &dealer, &rules),
+            Some((ResultsState::Won, 30))
+        );
+    }
+
+    #[test]
+    fn it_skips_resolved_hands() {
+        let mut player = hand(PlayerType::Player, &[Rank::Ten, Rank::Six]);
+        let dealer = hand(PlayerType::Dealer, &[Rank::Ten, Rank::Eight]);
+        player.set_surrendered();
+
+        assert_eq!(
+            settle_hand(&mut player, &dealer, &TableRules::default()),
+            None
+        );
+        assert_eq!(player.get_result_state(), &ResultsState::Surrendered);
+    }
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use crate::blackjack::table_rules::Payout;
+use crate::blackjack::wager::Wager;
+use crate::cards::Card;
+
+pub mod lucky_ladies;
+pub mod perfect_pairs;
+pub mod twenty_one_plus_three;
+
+// a bet on the player's first two cards and the dealer's cards that is settled
+// along with the hand, every side bet carries its own paytable
+pub trait SideBet: Send + Sync {
+    fn get_name(&self) -> &str;
+
+    // the payout for a winning combination, none when the bet loses. the
+    // dealer's first card is the upcard
+    fn evaluate(
+        &self,
+        player: &[Arc<Card>],
+        dealer: &[Arc<Card>],
+    ) -> Option<Payout>;
+}
+
+pub struct SideBetStake {
+    bet: Box<dyn SideBet>,
+    wager: Wager,
+    // the first two cards of the hand, kept once the hand is split
+    cards: Option<Vec<Arc<Card>>>,
+}
+
+impl SideBetStake {
+    pub fn new(bet: Box<dyn SideBet>, wager: u32) -> SideBetStake {
+        let mut stake = Wager::new();
+        stake.set_wager(wager);
+
+        SideBetStake {
+            bet,
+            wager: stake,
+            cards: None,
+        }
+    }
+
+    pub fn get_bet(&self) -> &dyn SideBet {
+        self.bet.as_ref()
+    }
+
+    pub fn get_wagered_value(&self) -> u32 {
+        self.wager.get_wager()
+    }
+
+    // only the first cards are kept, a hand can be split more than once
+    pub fn keep_cards(&mut self, cards: &[Arc<Card>]) {
+        if self.cards.is_none() {
+            self.cards = Some(cards.iter().take(2).cloned().collect());
+        }
+    }
+
+    // the amount paid back to the bankroll, including the stake
+    pub fn settle(&self, hand: &[Arc<Card>], dealer: &[Arc<Card>]) -> u32 {
+        let cards = match &self.cards {
+            Some(cards) => cards.as_slice(),
+            None => &hand[..hand.len().min(2)],
+        };
+
+        if cards.len() < 2 || dealer.is_empty() {
+            return 0;
+        }
+
+        let wager = self.wager.get_wager();
+
+        self.bet
+            .evaluate(cards, dealer)
+            .map_or(0, |payout| wager + payout.winnings(wager))
+    }
+}
+
+#[cfg(test)]
+pub mod test_support {
+    use std::sync::Arc;
+
+    use crate::blackjack::table_rules::Payout;
+    use crate::cards::parse::parse_cards;
+
+    use super::SideBet;
+
+    // evaluates a bet on the first two cards of the list, the rest are the
+    // dealer's
+    pub fn evaluate(bet: &dyn SideBet, cards: &str) -> Option<Payout> {
+        let cards: Vec<_> =
+            parse_cards(cards).unwrap().into_iter().map(Arc::new).collect();
+        bet.evaluate(&cards[..2], &cards[2..])
+    }
+}
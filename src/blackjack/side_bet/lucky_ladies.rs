@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use crate::blackjack::hand_value::HandValue;
+use crate::blackjack::side_bet::SideBet;
+use crate::blackjack::table_rules::Payout;
+use crate::cards::{Card, Rank, Suit};
+
+// pays when the player's first two cards total twenty
+#[derive(Clone, PartialEq, Debug)]
+pub struct LuckyLadies {
+    pub any_twenty: Payout,
+    pub suited_twenty: Payout,
+    // the same rank and suit
+    pub matched_twenty: Payout,
+    pub queen_of_hearts_pair: Payout,
+    // a pair of queen of hearts against a dealer blackjack
+    pub queen_of_hearts_pair_dealer_blackjack: Payout,
+}
+
+impl Default for LuckyLadies {
+    fn default() -> LuckyLadies {
+        LuckyLadies {
+            any_twenty: Payout::new(4, 1),
+            suited_twenty: Payout::new(10, 1),
+            matched_twenty: Payout::new(25, 1),
+            queen_of_hearts_pair: Payout::new(200, 1),
+            queen_of_hearts_pair_dealer_blackjack: Payout::new(1000, 1),
+        }
+    }
+}
+
+impl LuckyLadies {
+    pub fn new() -> LuckyLadies {
+        Default::default()
+    }
+}
+
+impl SideBet for LuckyLadies {
+    fn get_name(&self) -> &str {
+        "Lucky Ladies"
+    }
+
+    fn evaluate(
+        &self,
+        player: &[Arc<Card>],
+        dealer: &[Arc<Card>],
+    ) -> Option<Payout> {
+        let (first, second) = match player {
+            [first, second, ..] => (first, second),
+            _ => return None,
+        };
+
+        match HandValue::from_ranks(vec![first.rank, second.rank]) {
+            Some(HandValue::V(20)) | Some(HandValue::Ace(_, 20)) => {}
+            _ => return None,
+        }
+
+        let queen_of_hearts = |card: &Arc<Card>| {
+            card.rank == Rank::Queen && card.suit == Suit::Heart
+        };
+        let dealer_blackjack = dealer.len() == 2
+            && HandValue::from_ranks(dealer.iter().map(|card| card.rank))
+                == Some(HandValue::Ace(11, 21));
+
+        if queen_of_hearts(first) && queen_of_hearts(second) {
+            if dealer_blackjack {
+                Some(self.queen_of_hearts_pair_dealer_blackjack)
+            } else {
+                Some(self.queen_of_hearts_pair)
+            }
+        } else if first.rank == second.rank && first.suit == second.suit {
+            Some(self.matched_twenty)
+        } else if first.suit == second.suit {
+            Some(self.suited_twenty)
+        } else {
+            Some(self.any_twenty)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackjack::side_bet::test_support::evaluate;
+
+    use super::*;
+
+    #[test]
+    fn it_pays_twenty() {
+        let bet = LuckyLadies::new();
+
+        assert_eq!(evaluate(&bet, "KS 10H 2C 5D"), Some(Payout::new(4, 1)));
+        assert_eq!(evaluate(&bet, "AS 9H 2C 5D"), Some(Payout::new(4, 1)));
+        assert_eq!(evaluate(&bet, "KS JS 2C 5D"), Some(Payout::new(10, 1)));
+        assert_eq!(evaluate(&bet, "JD JD 2C 5D"), Some(Payout::new(25, 1)));
+        assert_eq!(evaluate(&bet, "KS 9H 2C 5D"), None);
+    }
+
+    #[test]
+    fn it_pays_queen_of_hearts_pair() {
+        let bet = LuckyLadies::new();
+
+        assert_eq!(evaluate(&bet, "QH QH 2C 5D"), Some(Payout::new(200, 1)));
+        assert_eq!(evaluate(&bet, "QH QH AC KD"), Some(Payout::new(1000, 1)));
+    }
+}
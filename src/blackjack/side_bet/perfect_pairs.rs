@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use crate::blackjack::side_bet::SideBet;
+use crate::blackjack::table_rules::Payout;
+use crate::cards::Card;
+
+// pays when the player's first two cards are a pair
+#[derive(Clone, PartialEq, Debug)]
+pub struct PerfectPairs {
+    // the same rank in different colors
+    pub mixed: Payout,
+    // the same rank and color but different suits
+    pub coloured: Payout,
+    // the same rank and suit
+    pub perfect: Payout,
+}
+
+impl Default for PerfectPairs {
+    fn default() -> PerfectPairs {
+        PerfectPairs {
+            mixed: Payout::new(6, 1),
+            coloured: Payout::new(12, 1),
+            perfect: Payout::new(25, 1),
+        }
+    }
+}
+
+impl PerfectPairs {
+    pub fn new() -> PerfectPairs {
+        Default::default()
+    }
+}
+
+impl SideBet for PerfectPairs {
+    fn get_name(&self) -> &str {
+        "Perfect Pairs"
+    }
+
+    fn evaluate(
+        &self,
+        player: &[Arc<Card>],
+        _dealer: &[Arc<Card>],
+    ) -> Option<Payout> {
+        let (first, second) = match player {
+            [first, second, ..] => (first, second),
+            _ => return None,
+        };
+
+        if first.rank != second.rank {
+            None
+        } else if first.suit == second.suit {
+            Some(self.perfect)
+        } else if first.suit.is_red() == second.suit.is_red() {
+            Some(self.coloured)
+        } else {
+            Some(self.mixed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackjack::side_bet::test_support::evaluate;
+
+    use super::*;
+
+    #[test]
+    fn it_pays_pairs() {
+        let bet = PerfectPairs::new();
+
+        assert_eq!(evaluate(&bet, "8S 8H 2C"), Some(Payout::new(6, 1)));
+        assert_eq!(evaluate(&bet, "8H 8D 2C"), Some(Payout::new(12, 1)));
+        assert_eq!(evaluate(&bet, "8C 8C 2C"), Some(Payout::new(25, 1)));
+        assert_eq!(evaluate(&bet, "8C 9C 2C"), None);
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use crate::blackjack::side_bet::SideBet;
+use crate::blackjack::table_rules::Payout;
+use crate::cards::{Card, Rank};
+
+// pays the three card poker hand made by the player's first two cards and the
+// dealer's upcard
+#[derive(Clone, PartialEq, Debug)]
+pub struct TwentyOnePlusThree {
+    pub flush: Payout,
+    pub straight: Payout,
+    pub three_of_a_kind: Payout,
+    pub straight_flush: Payout,
+    pub suited_trips: Payout,
+}
+
+impl Default for TwentyOnePlusThree {
+    fn default() -> TwentyOnePlusThree {
+        TwentyOnePlusThree {
+            flush: Payout::new(5, 1),
+            straight: Payout::new(10, 1),
+            three_of_a_kind: Payout::new(30, 1),
+            straight_flush: Payout::new(40, 1),
+            suited_trips: Payout::new(100, 1),
+        }
+    }
+}
+
+impl TwentyOnePlusThree {
+    pub fn new() -> TwentyOnePlusThree {
+        Default::default()
+    }
+}
+
+impl SideBet for TwentyOnePlusThree {
+    fn get_name(&self) -> &str {
+        "21+3"
+    }
+
+    fn evaluate(
+        &self,
+        player: &[Arc<Card>],
+        dealer: &[Arc<Card>],
+    ) -> Option<Payout> {
+        let cards = match (player, dealer.first()) {
+            ([first, second, ..], Some(upcard)) => [first, second, upcard],
+            _ => return None,
+        };
+
+        let suited = cards.iter().all(|card| card.suit == cards[0].suit);
+        let trips = cards.iter().all(|card| card.rank == cards[0].rank);
+        let straight = is_straight(&cards);
+
+        match (suited, trips, straight) {
+            (true, true, _) => Some(self.suited_trips),
+            (true, _, true) => Some(self.straight_flush),
+            (_, true, _) => Some(self.three_of_a_kind),
+            (_, _, true) => Some(self.straight),
+            (true, _, _) => Some(self.flush),
+            _ => None,
+        }
+    }
+}
+
+// aces are high or low, e.g. A-2-3 and Q-K-A but not K-A-2
+fn is_straight(cards: &[&Arc<Card>; 3]) -> bool {
+    let mut ranks: Vec<usize> = cards.iter().map(|c| c.rank.index()).collect();
+    ranks.sort();
+
+    let ace_high = [Rank::Ace.index(), Rank::Queen.index(), Rank::King.index()];
+
+    ranks == ace_high || (ranks[1] == ranks[0] + 1 && ranks[2] == ranks[1] + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackjack::side_bet::test_support::evaluate;
+
+    use super::*;
+
+    #[test]
+    fn it_pays_poker_hands() {
+        let bet = TwentyOnePlusThree::new();
+
+        assert_eq!(evaluate(&bet, "2S 9S KS"), Some(Payout::new(5, 1)));
+        assert_eq!(evaluate(&bet, "9S 10H JC"), Some(Payout::new(10, 1)));
+        assert_eq!(evaluate(&bet, "QS AH KC"), Some(Payout::new(10, 1)));
+        assert_eq!(evaluate(&bet, "3S AH 2C"), Some(Payout::new(10, 1)));
+        assert_eq!(evaluate(&bet, "7S 7H 7C"), Some(Payout::new(30, 1)));
+        assert_eq!(evaluate(&bet, "7D 8D 9D"), Some(Payout::new(40, 1)));
+        assert_eq!(evaluate(&bet, "7D 7D 7D"), Some(Payout::new(100, 1)));
+        assert_eq!(evaluate(&bet, "KS AH 2C"), None);
+        assert_eq!(evaluate(&bet, "KS 9H 2C"), None);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::blackjack::blackjack_hand::BlackjackHand;
+use crate::blackjack::hand_value::WithHandValue;
+use crate::blackjack::table_rules::Payout;
+use crate::cards::{Rank, Suit};
+
+// the spanish 21 bonus a winning 21 is paid at instead of even money. bonuses
+// are never paid on a doubled hand
+pub fn bonus_payout(hand: &BlackjackHand) -> Option<Payout> {
+    if hand.is_doubled() || hand.best_total() != Some(21) {
+        return None;
+    }
+
+    if let Some(payout) = six_seven_eight_payout(hand) {
+        return Some(payout);
+    }
+
+    match hand.get_card_count() {
+        5 => Some(Payout::three_to_two()),
+        6 => Some(Payout::new(2, 1)),
+        n if n >= 7 => Some(Payout::new(3, 1)),
+        _ => None,
+    }
+}
+
+// 6-7-8 or 7-7-7 pays 3:2 in mixed suits, 2:1 suited and 3:1 in spades
+fn six_seven_eight_payout(hand: &BlackjackHand) -> Option<Payout> {
+    let cards = hand.get_cards();
+
+    if cards.len() != 3 {
+        return None;
+    }
+
+    let mut ranks: Vec<usize> = cards.iter().map(|c| c.rank.index()).collect();
+    ranks.sort();
+
+    let six_seven_eight =
+        [Rank::Six.index(), Rank::Seven.index(), Rank::Eight.index()];
+    let seven_seven_seven = [Rank::Seven.index(); 3];
+
+    if ranks != six_seven_eight && ranks != seven_seven_seven {
+        return None;
+    }
+
+    let suit = cards[0].suit;
+
+    if cards.iter().any(|card| card.suit != suit) {
+        Some(Payout::three_to_two())
+    } else if suit == Suit::Spade {
+        Some(Payout::new(3, 1))
+    } else {
+        Some(Payout::new(2, 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::blackjack::player::PlayerType;
+    use crate::cards::parse::parse_cards;
+
+    use super::*;
+
+    fn hand(cards: &str) -> BlackjackHand {
+        let cards = parse_cards(cards).unwrap().into_iter().map(Arc::new);
+        BlackjackHand::with_cards(PlayerType::Player, cards.collect())
+    }
+
+    #[test]
+    fn it_pays_multi_card_21() {
+        assert_eq!(
+            bonus_payout(&hand("2S 3H 4D 5C 7S")),
+            Some(Payout::new(3, 2))
+        );
+        assert_eq!(
+            bonus_payout(&hand("2S 3H 4D 5C 2C 5S")),
+            Some(Payout::new(2, 1))
+        );
+        assert_eq!(
+            bonus_payout(&hand("2S 3H 4D AC 2C 4S 5H")),
+            Some(Payout::new(3, 1))
+        );
+        assert_eq!(bonus_payout(&hand("KS 5H 6D")), None);
+        assert_eq!(bonus_payout(&hand("2S 3H 4D 5C 6S")), None);
+    }
+
+    #[test]
+    fn it_pays_six_seven_eight() {
+        assert_eq!(bonus_payout(&hand("6S 7H 8D")), Some(Payout::new(3, 2)));
+        assert_eq!(bonus_payout(&hand("8H 6H 7H")), Some(Payout::new(2, 1)));
+        assert_eq!(bonus_payout(&hand("7S 7S 7S")), Some(Payout::new(3, 1)));
+        assert_eq!(bonus_payout(&hand("7S 7H 7D")), Some(Payout::new(3, 2)));
+    }
+
+    #[test]
+    fn it_does_not_pay_doubled_hands() {
+        let mut doubled = hand("6S 7S 8S");
+        doubled.double_down(10);
+
+        assert_eq!(bonus_payout(&doubled), None);
+    }
+}
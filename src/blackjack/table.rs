@@ -0,0 +1,397 @@
+use std::borrow::BorrowMut;
+use std::sync::Arc;
+
+use failure::Fail;
+use uuid::Uuid;
+
+use crate::blackjack::blackjack_hand::BlackjackHand;
+use crate::blackjack::hand_value::WithHandValue;
+use crate::blackjack::player::{ActionError, Player};
+use crate::blackjack::settlement::Settlement;
+use crate::blackjack::table_rules::{HoleCard, TableRules};
+use crate::cards::card::Card;
+use crate::cards::rank::Rank;
+use crate::cards::shoe::Shoe;
+
+#[derive(Debug, Fail)]
+pub enum DealingError {
+    #[fail(display = "Failed to take card from shoe")]
+    TakingCardFromShoe,
+    #[fail(display = "Failed to push card to player's current hand")]
+    PushCardToPlayer,
+}
+
+// the dealer's side of the game, every rule for dealing, peeking, playing the
+// dealer and settling a round lives here. the players are passed in so both
+// the synchronous and the channel driven game can share it
+pub struct Table {
+    dealer: Player,
+    shoe: Shoe,
+    rules: TableRules,
+    dealer_checked: bool,
+}
+
+impl Table {
+    pub fn new(rules: TableRules, shoe: Shoe) -> Table {
+        Table {
+            dealer: Player::new_dealer(String::from("Dealer")),
+            shoe,
+            rules,
+            dealer_checked: false,
+        }
+    }
+
+    pub fn get_rules(&self) -> &TableRules {
+        &self.rules
+    }
+
+    pub fn get_shoe(&self) -> &Shoe {
+        &self.shoe
+    }
+
+    pub fn shoe_mut(&mut self) -> &mut Shoe {
+        &mut self.shoe
+    }
+
+    pub fn get_dealer(&self) -> &Player {
+        &self.dealer
+    }
+
+    pub fn get_dealer_id(&self) -> Uuid {
+        self.dealer.get_id()
+    }
+
+    pub fn get_dealer_hand(&self) -> Option<&BlackjackHand> {
+        self.dealer.get_current_hand()
+    }
+
+    // burns a card for the new dealer, returns true when the burned card was
+    // the cut card and the shoe had to be reshuffled
+    pub fn change_dealer(&mut self) -> bool {
+        if !self.shoe.dealer_changed() || !self.shoe.is_cut_card_reached() {
+            return false;
+        }
+
+        self.shoe.reshuffle();
+        true
+    }
+
+    // returns true when the shoe was reshuffled because the cut card came out
+    pub fn start_round(&mut self) -> bool {
+        self.dealer_checked = false;
+        self.shoe.start_round()
+    }
+
+    // collects the cards from the finished round and clears every hand
+    pub fn end_round<P: BorrowMut<Player>>(&mut self, players: &mut [P]) {
+        self.shoe.end_round();
+        players.iter_mut().for_each(|p| p.borrow_mut().reset_hands());
+        self.dealer.reset_hands();
+        self.dealer_checked = false;
+    }
+
+    pub fn is_dealer_blackjack(&self) -> bool {
+        self.get_dealer_hand().map_or(false, |hand| hand.is_blackjack())
+    }
+
+    // returns true when the dealer has blackjack, once checked the players
+    // can no longer early surrender and late surrender becomes available
+    pub fn check_dealer_blackjack(&mut self) -> bool {
+        self.dealer_checked = true;
+        self.is_dealer_blackjack()
+    }
+
+    pub fn is_dealer_card_face_down(&self, card: &Arc<Card>) -> bool {
+        self.get_dealer_hand().map_or(false, |hand| {
+            !hand.get_face_up_cards().iter().any(|c| Arc::ptr_eq(c, card))
+        })
+    }
+
+    pub fn get_dealer_face_down_cards(&self) -> Vec<Arc<Card>> {
+        self.get_dealer_hand().map_or(vec![], |hand| {
+            hand.get_cards()
+                .iter()
+                .filter(|card| self.is_dealer_card_face_down(card))
+                .cloned()
+                .collect()
+        })
+    }
+
+    // deals two cards to every player and the dealer, the dealt cards are
+    // returned in order along with the id of the player that received them.
+    // without a hole card the dealer is only dealt the upcard, the second
+    // card is dealt once the players have acted
+    pub fn deal_cards<P: BorrowMut<Player>>(
+        &mut self,
+        players: &mut [P],
+    ) -> Result<Vec<(Uuid, Arc<Card>)>, DealingError> {
+        let mut dealt = vec![];
+
+        for round in 0..2 {
+            // blackjack switch players are dealt a card to each of their hands
+            for player in players.iter_mut() {
+                let player = player.borrow_mut();
+                for index in 0..player.get_hands().len() {
+                    let card = deal_card(&mut self.shoe, player, index)?;
+                    dealt.push((player.get_id(), card));
+                }
+            }
+
+            let dealer_id = self.dealer.get_id();
+
+            if self.rules.dealer_cards_face_down {
+                dealt.push((dealer_id, self.deal_card_to_dealer(false)?));
+            } else if round == 0 || self.rules.dealer_cards_exposed {
+                dealt.push((dealer_id, self.deal_card_to_dealer(true)?));
+            } else if self.rules.hole_card == HoleCard::Peek {
+                dealt.push((dealer_id, self.deal_card_to_dealer(false)?));
+            }
+        }
+
+        Ok(dealt)
+    }
+
+    // checks the hole card under an ace or ten-value upcard, when the dealer
+    // has blackjack the cards are turned over, the round is over and no
+    // player gets to act
+    pub fn peek<P: BorrowMut<Player>>(&mut self, players: &mut [P]) -> bool {
+        if self.rules.hole_card != HoleCard::Peek {
+            return false;
+        }
+
+        let should_peek =
+            self.get_dealer_hand().map_or(false, |hand| hand.should_peek());
+
+        if !should_peek {
+            self.dealer_checked = true;
+            return false;
+        }
+
+        let dealer_natural = self.check_dealer_blackjack();
+
+        if dealer_natural {
+            self.dealer.reveal_cards();
+            players.iter_mut().for_each(|p| p.borrow_mut().end_turn());
+        }
+
+        dealer_natural
+    }
+
+    // deals the dealer's second card when playing without a hole card, only
+    // once the upcard is out. under original bets only the players get their
+    // doubles and splits back when it gives the dealer blackjack
+    pub fn deal_dealer_second_card<P: BorrowMut<Player>>(
+        &mut self,
+        players: &mut [P],
+    ) -> Result<Option<Arc<Card>>, DealingError> {
+        let dealt = self.get_dealer_hand().map_or(0, |h| h.get_card_count());
+
+        if self.rules.hole_card == HoleCard::Peek || dealt != 1 {
+            return Ok(None);
+        }
+
+        let card = self.deal_card_to_dealer(true)?;
+
+        if self.check_dealer_blackjack()
+            && self.rules.hole_card == HoleCard::OriginalBetsOnly
+        {
+            players.iter_mut().for_each(|p| {
+                p.borrow_mut().refund_additional_bets();
+            });
+        }
+
+        Ok(Some(card))
+    }
+
+    // insurance is only offered when the dealer shows an ace and the hole
+    // card is hidden
+    pub fn is_insurance_offered(&self) -> bool {
+        self.rules.hole_card == HoleCard::Peek
+            && !self.rules.dealer_cards_exposed
+            && self
+                .get_dealer_hand()
+                .and_then(|hand| hand.get_upcard())
+                .map_or(false, |card| card.rank == Rank::Ace)
+    }
+
+    pub fn place_insurance(
+        &mut self,
+        player: &mut Player,
+        wager: u32,
+    ) -> Result<(), ActionError> {
+        if !self.is_insurance_offered() || self.dealer_checked {
+            return Err(ActionError::NotAllowed);
+        }
+        player.place_insurance(wager)
+    }
+
+    pub fn take_even_money(
+        &mut self,
+        player: &mut Player,
+    ) -> Result<u32, ActionError> {
+        if !self.is_insurance_offered() || self.dealer_checked {
+            return Err(ActionError::NotAllowed);
+        }
+        player.take_even_money()
+    }
+
+    // closes the insurance window by checking the hole card, returns true
+    // when the dealer has blackjack
+    pub fn settle_insurance<P: BorrowMut<Player>>(
+        &mut self,
+        players: &mut [P],
+    ) -> bool {
+        let dealer_natural = self.peek(players);
+        players.iter_mut().for_each(|p| {
+            p.borrow_mut().settle_insurance(dealer_natural);
+        });
+        dealer_natural
+    }
+
+    // turns the dealer's cards over and draws until the hand stands, the
+    // dealt cards are returned. without a hole card the second card is dealt
+    // first if it hasn't been already
+    pub fn play_dealer<P: BorrowMut<Player>>(
+        &mut self,
+        players: &mut [P],
+    ) -> Result<Vec<Arc<Card>>, ActionError> {
+        let mut dealt = vec![];
+
+        if let Some(card) = self
+            .deal_dealer_second_card(players)
+            .map_err(|_| ActionError::TakingCardFromShoe)?
+        {
+            dealt.push(card);
+        }
+
+        self.dealer.reveal_cards();
+
+        while self
+            .get_dealer_hand()
+            .map_or(false, |hand| hand.can_hit(&self.rules))
+        {
+            dealt.push(self.dealer.hit(&mut self.shoe, &self.rules)?);
+        }
+
+        Ok(dealt)
+    }
+
+    // settles every player's hands against the dealer and pays out
+    pub fn settle_round<P: BorrowMut<Player>>(
+        &mut self,
+        players: &mut [P],
+    ) -> Vec<Settlement> {
+        let dealer = match self.dealer.get_current_hand() {
+            Some(hand) => hand,
+            None => return vec![],
+        };
+        let rules = &self.rules;
+
+        players
+            .iter_mut()
+            .flat_map(|p| p.borrow_mut().settle(dealer, rules))
+            .collect()
+    }
+
+    pub fn hit(
+        &mut self,
+        player: &mut Player,
+    ) -> Result<Arc<Card>, ActionError> {
+        player.hit(&mut self.shoe, &self.rules)
+    }
+
+    pub fn twist(
+        &mut self,
+        player: &mut Player,
+    ) -> Result<Arc<Card>, ActionError> {
+        player.twist(&mut self.shoe, &self.rules)
+    }
+
+    pub fn stick(&mut self, player: &mut Player) -> Result<(), ActionError> {
+        player.stick(&self.rules)
+    }
+
+    pub fn buy(
+        &mut self,
+        player: &mut Player,
+        wager: u32,
+    ) -> Result<Arc<Card>, ActionError> {
+        player.buy(wager, &mut self.shoe, &self.rules)
+    }
+
+    pub fn double_down(
+        &mut self,
+        player: &mut Player,
+    ) -> Result<Arc<Card>, ActionError> {
+        player.double_down(&mut self.shoe, &self.rules)
+    }
+
+    pub fn double_for_less(
+        &mut self,
+        player: &mut Player,
+        wager: u32,
+    ) -> Result<Arc<Card>, ActionError> {
+        player.double_for_less(wager, &mut self.shoe, &self.rules)
+    }
+
+    pub fn split(
+        &mut self,
+        player: &mut Player,
+    ) -> Result<Vec<Arc<Card>>, ActionError> {
+        player.split(&mut self.shoe, &self.rules)
+    }
+
+    pub fn place_switch_wagers(
+        &mut self,
+        player: &mut Player,
+        wager: u32,
+    ) -> Result<(), ActionError> {
+        player.place_switch_wagers(wager, &self.rules)
+    }
+
+    // returns the double down wager that was refunded to the player
+    pub fn rescue(&mut self, player: &mut Player) -> Result<u32, ActionError> {
+        player.rescue(&self.rules)
+    }
+
+    // returns the half of the wager that was refunded to the player
+    pub fn surrender(
+        &mut self,
+        player: &mut Player,
+    ) -> Result<u32, ActionError> {
+        player.surrender(&self.rules, self.dealer_checked)
+    }
+
+    // the hole card is dealt face down
+    fn deal_card_to_dealer(
+        &mut self,
+        face_up: bool,
+    ) -> Result<Arc<Card>, DealingError> {
+        let card =
+            self.shoe.take_card().ok_or(DealingError::TakingCardFromShoe)?;
+
+        let pushed = if face_up {
+            self.dealer.push_card_to_current_hand(card.clone())
+        } else {
+            self.dealer.push_card_face_down_to_current_hand(card.clone())
+        };
+
+        pushed.map_err(|_| DealingError::PushCardToPlayer)?;
+
+        Ok(card)
+    }
+}
+
+fn deal_card(
+    shoe: &mut Shoe,
+    player: &mut Player,
+    hand_index: usize,
+) -> Result<Arc<Card>, DealingError> {
+    let card = shoe.take_card().ok_or(DealingError::TakingCardFromShoe)?;
+
+    player
+        .push_card_to_hand(hand_index, card.clone())
+        .map_err(|_| DealingError::PushCardToPlayer)?;
+
+    Ok(card)
+}
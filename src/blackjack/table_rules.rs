@@ -0,0 +1,278 @@
+use crate::cards::deck_spec::DeckSpec;
+
+// how much a winning hand is paid for every unit wagered, e.g. 3:2
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Payout {
+    pub numerator: u32,
+    pub denominator: u32,
+}
+
+impl Payout {
+    pub fn new(numerator: u32, denominator: u32) -> Payout {
+        Payout {
+            numerator,
+            denominator,
+        }
+    }
+
+    pub fn three_to_two() -> Payout {
+        Payout::new(3, 2)
+    }
+
+    pub fn six_to_five() -> Payout {
+        Payout::new(6, 5)
+    }
+
+    pub fn even_money() -> Payout {
+        Payout::new(1, 1)
+    }
+
+    // the winnings for the wager, not including the wager itself. fractions of
+    // a unit are rounded down in favor of the house
+    pub fn winnings(&self, wager: u32) -> u32 {
+        (u64::from(wager) * u64::from(self.numerator)
+            / u64::from(self.denominator)) as u32
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum DoubleDown {
+    AnyTwoCards,
+    // only on a hard 9, 10 or 11
+    NineToEleven,
+    // only on a hard 10 or 11
+    TenToEleven,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Surrender {
+    None,
+    // only once the dealer has checked for blackjack
+    Late,
+    // before the dealer checks for blackjack
+    Early,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum HoleCard {
+    // the dealer peeks under an ace or ten-value upcard for blackjack
+    Peek,
+    // european no hole card, the dealer's second card is dealt after the
+    // players act and doubles and splits are lost to a dealer blackjack
+    NoHoleCard,
+    // no hole card, but only the original bet is lost to a dealer blackjack
+    OriginalBetsOnly,
+}
+
+// a player hand that reaches the number of cards without busting wins
+// automatically, some tables pay a bonus on it instead of even money
+#[derive(Clone, PartialEq, Debug)]
+pub struct Charlie {
+    pub cards: usize,
+    pub bonus: Option<Payout>,
+}
+
+impl Charlie {
+    pub fn new(cards: usize) -> Charlie {
+        Charlie {
+            cards,
+            bonus: None,
+        }
+    }
+
+    pub fn with_bonus(cards: usize, bonus: Payout) -> Charlie {
+        Charlie {
+            cards,
+            bonus: Some(bonus),
+        }
+    }
+}
+
+// the house rules for a table, every legality check for a hand and the dealing
+// of a game consults these
+#[derive(Clone, PartialEq, Debug)]
+pub struct TableRules {
+    pub decks: u32,
+    pub deck: DeckSpec,
+    pub seats: usize,
+    pub dealer_hits_soft_17: bool,
+    pub double_down: DoubleDown,
+    pub double_after_split: bool,
+    // doubling is allowed after hitting, not only on the first two cards
+    pub double_any_number_of_cards: bool,
+    // after doubling the player can take back the double and forfeit the
+    // original wager instead of standing
+    pub double_down_rescue: bool,
+    // the number of times a player can split, 3 splits is at most 4 hands
+    pub max_splits: usize,
+    pub resplit_aces: bool,
+    // allows splitting any two ten-value cards, e.g. a king and a queen
+    pub split_unlike_tens: bool,
+    // whether an ace and a ten-value card after a split is paid as a natural
+    pub natural_after_split: bool,
+    pub surrender: Surrender,
+    pub hole_card: HoleCard,
+    pub blackjack_payout: Payout,
+    // a player's 21, and blackjack, beats the dealer's
+    pub player_21_always_wins: bool,
+    // the spanish 21 bonuses for five or more card 21, 6-7-8 and 7-7-7
+    pub bonus_21_payouts: bool,
+    // every player plays two hands and can switch their second cards
+    pub switch_hands: bool,
+    // a dealer 22 pushes every player hand that hasn't busted
+    pub dealer_22_pushes: bool,
+    // the house stakes the double on a hard 9, 10 or 11
+    pub free_doubles: bool,
+    // the house stakes the new hand when splitting any pair but tens
+    pub free_splits: bool,
+    // double exposure, both of the dealer's cards are dealt face up
+    pub dealer_cards_exposed: bool,
+    pub dealer_wins_ties: bool,
+    // a player's natural still wins a tie when the dealer wins ties
+    pub player_natural_wins_ties: bool,
+    // e.g. a five card charlie, no more cards can be taken once it's reached
+    pub charlie: Option<Charlie>,
+    // pontoon, both of the dealer's cards are dealt face down
+    pub dealer_cards_face_down: bool,
+    // the lowest total a player can stand (stick) on
+    pub min_stand_total: Option<u8>,
+    // pontoon, the player can buy a card by raising the stake by up to the
+    // original wager, but not after twisting
+    pub buy_cards: bool,
+}
+
+impl Default for TableRules {
+    fn default() -> TableRules {
+        TableRules {
+            decks: 6,
+            deck: DeckSpec::standard(),
+            seats: 7,
+            dealer_hits_soft_17: true,
+            double_down: DoubleDown::AnyTwoCards,
+            double_after_split: true,
+            double_any_number_of_cards: false,
+            double_down_rescue: false,
+            max_splits: 3,
+            resplit_aces: false,
+            split_unlike_tens: true,
+            natural_after_split: false,
+            surrender: Surrender::None,
+            hole_card: HoleCard::Peek,
+            blackjack_payout: Payout::three_to_two(),
+            player_21_always_wins: false,
+            bonus_21_payouts: false,
+            switch_hands: false,
+            dealer_22_pushes: false,
+            free_doubles: false,
+            free_splits: false,
+            dealer_cards_exposed: false,
+            dealer_wins_ties: false,
+            player_natural_wins_ties: false,
+            charlie: None,
+            dealer_cards_face_down: false,
+            min_stand_total: None,
+            buy_cards: false,
+        }
+    }
+}
+
+impl TableRules {
+    pub fn new() -> TableRules {
+        Default::default()
+    }
+
+    // spanish 21 is dealt from decks without the tens, the player's 21 always
+    // wins and pays a bonus for some hands
+    pub fn spanish_21() -> TableRules {
+        TableRules {
+            deck: DeckSpec::spanish(),
+            surrender: Surrender::Late,
+            double_any_number_of_cards: true,
+            double_down_rescue: true,
+            resplit_aces: true,
+            player_21_always_wins: true,
+            bonus_21_payouts: true,
+            ..Default::default()
+        }
+    }
+
+    // blackjack switch pays naturals even money and pushes on a dealer 22 to
+    // make up for the switch
+    pub fn blackjack_switch() -> TableRules {
+        TableRules {
+            switch_hands: true,
+            dealer_22_pushes: true,
+            blackjack_payout: Payout::even_money(),
+            ..Default::default()
+        }
+    }
+
+    // free bet blackjack pays for some doubles and splits and pushes on a
+    // dealer 22
+    pub fn free_bet() -> TableRules {
+        TableRules {
+            free_doubles: true,
+            free_splits: true,
+            dealer_22_pushes: true,
+            ..Default::default()
+        }
+    }
+
+    // double exposure shows both dealer cards, in exchange naturals only pay
+    // even money and the dealer wins ties
+    pub fn double_exposure() -> TableRules {
+        TableRules {
+            dealer_cards_exposed: true,
+            dealer_wins_ties: true,
+            player_natural_wins_ties: true,
+            blackjack_payout: Payout::even_money(),
+            ..Default::default()
+        }
+    }
+
+    // british pontoon, a pontoon and a five card trick pay 2:1 but the
+    // dealer's cards are hidden and the dealer wins ties
+    pub fn pontoon() -> TableRules {
+        TableRules {
+            dealer_cards_face_down: true,
+            dealer_wins_ties: true,
+            min_stand_total: Some(15),
+            buy_cards: true,
+            blackjack_payout: Payout::new(2, 1),
+            charlie: Some(Charlie::with_bonus(5, Payout::new(2, 1))),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_pays_blackjack() {
+        assert_eq!(Payout::three_to_two().winnings(10), 15);
+        assert_eq!(Payout::six_to_five().winnings(10), 12);
+        assert_eq!(Payout::even_money().winnings(10), 10);
+        assert_eq!(Payout::three_to_two().winnings(5), 7);
+    }
+
+    #[test]
+    fn it_defaults_to_six_decks_and_seven_seats() {
+        let rules = TableRules::new();
+
+        assert_eq!(rules.decks, 6);
+        assert_eq!(rules.seats, 7);
+        assert!(rules.dealer_hits_soft_17);
+        assert_eq!(rules.blackjack_payout, Payout::three_to_two());
+    }
+
+    #[test]
+    fn it_uses_spanish_deck_for_spanish_21() {
+        let rules = TableRules::spanish_21();
+
+        assert_eq!(rules.deck.len(), 48);
+        assert_eq!(rules.surrender, Surrender::Late);
+        assert!(rules.player_21_always_wins);
+    }
+}
@@ -59,6 +59,26 @@ impl Ui {
                     Event::SetPlayerBankroll(value) => {
                         handlers::set_player_bankroll(&mut self.backend, value);
                     }
+                    Event::ShoeReshuffled => {
+                        info!("The shoe has been reshuffled");
+                    }
+                    Event::CardDealt(result) => match result {
+                        Ok((_, card)) => info!("Dealt {}", card),
+                        Err(e) => error!("Failed to deal cards: {}", e),
+                    },
+                    Event::HoleCardDealt(_) => info!("Dealt a card face down"),
+                    Event::CardRevealed(_, card) => info!("Revealed {}", card),
+                    Event::InsuranceOffered => {
+                        info!("Dealer shows an ace, insurance is open");
+                    }
+                    Event::DealerBlackjack => info!("Dealer has blackjack"),
+                    Event::HandSettled(settlement) => info!(
+                        "Hand {} {:?} ({})",
+                        settlement.hand_index + 1,
+                        settlement.result,
+                        settlement.get_net()
+                    ),
+                    Event::ActionFailed(e) => error!("{}", e),
                 }
                 self.backend.refresh();
             }
@@ -1,11 +1,16 @@
+// the wager is what the player staked from their bankroll, the free wager is
+// staked by the house (free bet doubles and splits). winnings are paid on both
+// but only the player's own stake is ever returned or lost
 pub struct Wager {
     wager: u32,
+    free: u32,
 }
 
 impl Wager {
     pub fn new() -> Wager {
         Wager {
             wager: 0,
+            free: 0,
         }
     }
 
@@ -19,11 +24,25 @@ impl Wager {
 
     pub fn reset_wager(&mut self) {
         self.wager = 0;
+        self.free = 0;
     }
 
     pub fn add_wager(&mut self, wager: u32) {
         self.wager += wager;
     }
+
+    pub fn get_free_wager(&self) -> u32 {
+        self.free
+    }
+
+    pub fn add_free_wager(&mut self, wager: u32) {
+        self.free += wager;
+    }
+
+    // the amount in action, including the free part
+    pub fn get_total_wager(&self) -> u32 {
+        self.wager + self.free
+    }
 }
 
 pub trait WithWager {
@@ -45,4 +64,16 @@ pub trait WithWager {
     fn add_wager_to_wagered_value(&mut self, wager: u32) {
         self.get_mut_wager().add_wager(wager);
     }
+
+    fn get_free_wagered_value(&self) -> u32 {
+        self.get_wager().get_free_wager()
+    }
+
+    fn add_free_wager_to_wagered_value(&mut self, wager: u32) {
+        self.get_mut_wager().add_free_wager(wager);
+    }
+
+    fn get_total_wagered_value(&self) -> u32 {
+        self.get_wager().get_total_wager()
+    }
 }
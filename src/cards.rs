@@ -1,10 +1,15 @@
 pub mod bankroll;
 pub mod card;
+pub mod compact;
 pub mod deck;
+pub mod deck_spec;
+pub mod discard_tray;
 pub mod hand;
+pub mod parse;
 pub mod player;
 pub mod rank;
 pub mod shoe;
+pub mod shuffle;
 pub mod shuffleable;
 pub mod suit;
 
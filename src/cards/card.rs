@@ -1,5 +1,7 @@
 use std::fmt::{Debug, Display, Formatter, Result};
+use std::str::FromStr;
 
+use crate::cards::parse::ParseCardError;
 use crate::cards::rank::Rank;
 use crate::cards::suit::Suit;
 
@@ -45,3 +47,30 @@ impl Card {
         }
     }
 }
+
+// accepts the rank and suit in either order, so the display form of a card
+// can be read back in, e.g. "A\u{2660}", "\u{2660}A", "10h" or "Td"
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> std::result::Result<Card, ParseCardError> {
+        let mut chars = s.chars();
+
+        let (first, last) = match (chars.next(), chars.next_back()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(ParseCardError::InvalidCard(s.to_string())),
+        };
+
+        if let Ok(suit) = last.to_string().parse::<Suit>() {
+            let rank = &s[..s.len() - last.len_utf8()];
+            return Ok(Card::from(suit, rank.parse()?));
+        }
+
+        if let Ok(suit) = first.to_string().parse::<Suit>() {
+            let rank = &s[first.len_utf8()..];
+            return Ok(Card::from(suit, rank.parse()?));
+        }
+
+        Err(ParseCardError::InvalidSuit(s.to_string()))
+    }
+}
@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use crate::cards::card::Card;
+use crate::cards::rank::Rank;
+use crate::cards::suit::Suit;
+
+pub const CARD_INDEX_COUNT: u8 = 52;
+
+// a card packed into a single byte, `suit * 13 + rank`, for the hot loops that
+// can't afford an `Arc<Card>` per card
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CompactCard(u8);
+
+impl CompactCard {
+    pub fn from_index(index: u8) -> Option<CompactCard> {
+        if index < CARD_INDEX_COUNT {
+            Some(CompactCard(index))
+        } else {
+            None
+        }
+    }
+
+    pub fn index(self) -> u8 {
+        self.0
+    }
+
+    pub fn rank(self) -> Rank {
+        Rank::from_index((self.0 % 13) as usize).unwrap()
+    }
+
+    pub fn suit(self) -> Suit {
+        Suit::from_index((self.0 / 13) as usize).unwrap()
+    }
+
+    pub fn to_card(self) -> Card {
+        Card::from(self.suit(), self.rank())
+    }
+}
+
+impl From<&Card> for CompactCard {
+    fn from(card: &Card) -> CompactCard {
+        CompactCard((card.suit.index() * 13 + card.rank.index()) as u8)
+    }
+}
+
+impl From<CompactCard> for Card {
+    fn from(card: CompactCard) -> Card {
+        card.to_card()
+    }
+}
+
+// a set of distinct cards stored as a bitset, only useful for a single deck
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    pub fn new() -> CardSet {
+        Default::default()
+    }
+
+    // returns false when the card was already in the set
+    pub fn insert(&mut self, card: CompactCard) -> bool {
+        let had_card = self.contains(card);
+        self.0 |= 1 << card.index();
+        !had_card
+    }
+
+    // returns false when the card was not in the set
+    pub fn remove(&mut self, card: CompactCard) -> bool {
+        let had_card = self.contains(card);
+        self.0 &= !(1 << card.index());
+        had_card
+    }
+
+    pub fn contains(&self, card: CompactCard) -> bool {
+        self.0 & (1 << card.index()) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = CompactCard> {
+        let bits = self.0;
+        (0..CARD_INDEX_COUNT)
+            .filter(move |index| bits & (1 << index) != 0)
+            .map(CompactCard)
+    }
+
+    pub fn rank_counts(&self) -> RankCounts {
+        let mut counts = RankCounts::new();
+        self.iter().for_each(|card| counts.add(card.rank()));
+        counts
+    }
+}
+
+// the number of cards of each rank, enough to value a hand or describe the
+// composition of a shoe without keeping the cards around
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct RankCounts {
+    counts: [u32; 13],
+    len: u32,
+}
+
+impl RankCounts {
+    pub fn new() -> RankCounts {
+        Default::default()
+    }
+
+    pub fn from_cards<'c, I>(cards: I) -> RankCounts
+    where
+        I: IntoIterator<Item = &'c Arc<Card>>,
+    {
+        let mut counts = RankCounts::new();
+        cards.into_iter().for_each(|card| counts.add(card.rank));
+        counts
+    }
+
+    pub fn add(&mut self, rank: Rank) {
+        self.counts[rank.index()] += 1;
+        self.len += 1;
+    }
+
+    // returns false when there wasn't a card of the rank to remove
+    pub fn remove(&mut self, rank: Rank) -> bool {
+        match self.counts[rank.index()].checked_sub(1) {
+            Some(count) => {
+                self.counts[rank.index()] = count;
+                self.len -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn get(&self, rank: Rank) -> u32 {
+        self.counts[rank.index()]
+    }
+
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.counts = [0; 13];
+        self.len = 0;
+    }
+
+    // every rank once for each card of that rank
+    pub fn ranks(&self) -> impl Iterator<Item = Rank> + '_ {
+        Rank::iter().flat_map(move |rank| {
+            (0..self.counts[rank.index()]).map(move |_| *rank)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::deck::Deck;
+
+    use super::*;
+
+    #[test]
+    fn it_round_trips_every_card() {
+        let deck = Deck::new();
+        let mut seen = CardSet::new();
+
+        deck.cards.iter().for_each(|card| {
+            let compact = CompactCard::from(card.as_ref());
+
+            assert!(compact.index() < CARD_INDEX_COUNT);
+            assert_eq!(compact.to_card(), **card);
+            assert!(seen.insert(compact));
+        });
+
+        assert_eq!(seen.len(), 52);
+        assert!(CompactCard::from_index(CARD_INDEX_COUNT).is_none());
+    }
+
+    #[test]
+    fn it_tracks_cards_in_set() {
+        let ace = CompactCard::from(&Card::from(Suit::Spade, Rank::Ace));
+        let king = CompactCard::from(&Card::from(Suit::Club, Rank::King));
+
+        let mut set = CardSet::new();
+        assert!(set.is_empty());
+
+        assert!(set.insert(ace));
+        assert!(!set.insert(ace));
+        assert!(set.insert(king));
+
+        assert_eq!(set.iter().collect::<Vec<CompactCard>>(), vec![ace, king]);
+        assert_eq!(set.rank_counts().get(Rank::Ace), 1);
+
+        assert!(set.remove(ace));
+        assert!(!set.remove(ace));
+        assert!(!set.contains(ace));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn it_counts_ranks() {
+        let mut counts = RankCounts::from_cards(Deck::new().cards.iter());
+
+        assert_eq!(counts.len(), 52);
+        assert_eq!(counts.get(Rank::Ten), 4);
+
+        assert!(counts.remove(Rank::Ten));
+        assert_eq!(counts.get(Rank::Ten), 3);
+        assert_eq!(counts.len(), 51);
+        assert_eq!(counts.ranks().count(), 51);
+
+        counts.clear();
+
+        assert!(counts.is_empty());
+        assert!(!counts.remove(Rank::Ten));
+    }
+}
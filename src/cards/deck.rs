@@ -1,9 +1,10 @@
 use crate::cards::card::Card;
-use crate::cards::rank::Rank;
+use crate::cards::deck_spec::DeckSpec;
 use crate::cards::shuffleable::Shuffleable;
-use crate::cards::suit::Suit;
 use std::sync::Arc;
 
+pub const CARDS_PER_DECK: usize = 52;
+
 #[derive(Debug)]
 pub struct Deck {
     pub cards: Vec<Arc<Card>>,
@@ -11,19 +12,12 @@ pub struct Deck {
 
 impl Deck {
     pub fn new() -> Deck {
-        let mut cards: Vec<Arc<Card>> = Vec::with_capacity(52);
-
-        Suit::iter().for_each(|&suit| {
-            Rank::iter().for_each(|&rank| {
-                cards.push(Arc::new(Card {
-                    suit,
-                    rank,
-                }));
-            })
-        });
+        Deck::from_spec(&DeckSpec::standard())
+    }
 
+    pub fn from_spec(spec: &DeckSpec) -> Deck {
         Deck {
-            cards,
+            cards: spec.cards().into_iter().map(Arc::new).collect(),
         }
     }
 }
@@ -33,3 +27,42 @@ impl Shuffleable for Deck {
         &mut self.cards
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn builds_standard_deck() {
+        let deck = Deck::new();
+
+        assert_eq!(deck.cards.len(), CARDS_PER_DECK);
+        assert_eq!(deck.cards.first().unwrap().to_string(), "\u{2660}A");
+    }
+
+    #[test]
+    fn shuffles_with_seed() {
+        let mut deck = Deck::new();
+        let mut other = Deck::new();
+
+        deck.shuffle_with_seed(7);
+        other.shuffle_with_seed(7);
+
+        assert_eq!(deck.cards, other.cards);
+    }
+
+    #[test]
+    fn shuffles_with_injected_rng() {
+        let mut deck = Deck::new();
+        let mut other = Deck::new();
+
+        deck.shuffle_rounds_with_rng(1, &mut StdRng::seed_from_u64(7));
+        other.shuffle_rounds_with_rng(1, &mut StdRng::seed_from_u64(7));
+
+        assert_eq!(deck.cards, other.cards);
+        assert_ne!(deck.cards, Deck::new().cards);
+    }
+}
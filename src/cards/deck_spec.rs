@@ -0,0 +1,151 @@
+use crate::cards::card::Card;
+use crate::cards::rank::Rank;
+use crate::cards::suit::Suit;
+
+// describes the cards in a single deck, the standard deck is every suit for
+// every rank. ranks can be stripped and specific cards added or removed
+#[derive(Clone, PartialEq, Debug)]
+pub struct DeckSpec {
+    suits: Vec<Suit>,
+    ranks: Vec<Rank>,
+    added_cards: Vec<Card>,
+    removed_cards: Vec<Card>,
+}
+
+impl Default for DeckSpec {
+    fn default() -> DeckSpec {
+        DeckSpec {
+            suits: Suit::iter().cloned().collect(),
+            ranks: Rank::iter().cloned().collect(),
+            added_cards: vec![],
+            removed_cards: vec![],
+        }
+    }
+}
+
+impl DeckSpec {
+    pub fn standard() -> DeckSpec {
+        Default::default()
+    }
+
+    // the 48 card spanish deck has all of the tens removed, the face cards stay
+    pub fn spanish() -> DeckSpec {
+        DeckSpec::standard().without_rank(Rank::Ten)
+    }
+
+    // a deck made of exactly the given cards
+    pub fn custom(cards: Vec<Card>) -> DeckSpec {
+        DeckSpec {
+            suits: vec![],
+            ranks: vec![],
+            added_cards: cards,
+            removed_cards: vec![],
+        }
+    }
+
+    pub fn without_rank(mut self, rank: Rank) -> DeckSpec {
+        self.ranks.retain(|r| *r != rank);
+        self
+    }
+
+    pub fn without_suit(mut self, suit: Suit) -> DeckSpec {
+        self.suits.retain(|s| *s != suit);
+        self
+    }
+
+    pub fn with_card(mut self, card: Card) -> DeckSpec {
+        self.added_cards.push(card);
+        self
+    }
+
+    // removes a single copy of the card from the deck
+    pub fn without_card(mut self, card: Card) -> DeckSpec {
+        self.removed_cards.push(card);
+        self
+    }
+
+    pub fn cards(&self) -> Vec<Card> {
+        let mut cards: Vec<Card> = Vec::with_capacity(
+            self.suits.len() * self.ranks.len() + self.added_cards.len(),
+        );
+
+        self.suits.iter().for_each(|&suit| {
+            self.ranks.iter().for_each(|&rank| {
+                cards.push(Card::from(suit, rank));
+            })
+        });
+
+        cards.extend(self.added_cards.iter().cloned());
+
+        self.removed_cards.iter().for_each(|removed| {
+            if let Some(pos) = cards.iter().position(|card| card == removed) {
+                cards.remove(pos);
+            }
+        });
+
+        cards
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_builds_standard_deck() {
+        let spec = DeckSpec::standard();
+
+        assert_eq!(spec.len(), 52);
+        assert_eq!(spec.cards().len(), 52);
+    }
+
+    #[test]
+    fn it_builds_spanish_deck() {
+        let spec = DeckSpec::spanish();
+        let cards = spec.cards();
+
+        assert_eq!(spec.len(), 48);
+        assert_eq!(cards.len(), 48);
+        assert!(!cards.iter().any(|card| card.rank == Rank::Ten));
+        assert!(cards.iter().any(|card| card.rank == Rank::King));
+    }
+
+    #[test]
+    fn it_adds_and_removes_cards() {
+        let ace = Card::from(Suit::Spade, Rank::Ace);
+        let extra_ten = Card::from(Suit::Heart, Rank::Ten);
+
+        let spec = DeckSpec::spanish()
+            .without_card(ace)
+            .with_card(extra_ten)
+            .without_card(Card::from(Suit::Club, Rank::Ten));
+        let cards = spec.cards();
+
+        assert_eq!(spec.len(), 48);
+        assert_eq!(cards.len(), 48);
+        assert!(!cards.contains(&ace));
+        assert!(cards.contains(&extra_ten));
+    }
+
+    #[test]
+    fn it_builds_custom_deck() {
+        let cards = vec![
+            Card::from(Suit::Spade, Rank::Ace),
+            Card::from(Suit::Spade, Rank::Ace),
+            Card::from(Suit::Heart, Rank::Five),
+        ];
+
+        let spec = DeckSpec::custom(cards.clone());
+
+        assert_eq!(spec.len(), 3);
+        assert_eq!(spec.cards(), cards);
+    }
+}
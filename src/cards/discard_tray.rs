@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use crate::cards::card::Card;
+use crate::cards::compact::RankCounts;
+use crate::cards::rank::Rank;
+
+// collects the cards from finished rounds until the shoe is reshuffled
+#[derive(Debug)]
+pub struct DiscardTray {
+    cards: Vec<Arc<Card>>,
+    rank_counts: RankCounts,
+}
+
+impl Default for DiscardTray {
+    fn default() -> DiscardTray {
+        DiscardTray {
+            cards: Vec::new(),
+            rank_counts: RankCounts::new(),
+        }
+    }
+}
+
+impl DiscardTray {
+    pub fn new() -> DiscardTray {
+        Default::default()
+    }
+
+    pub fn add_card(&mut self, card: Arc<Card>) {
+        self.rank_counts.add(card.rank);
+        self.cards.push(card);
+    }
+
+    pub fn add_cards(&mut self, cards: Vec<Arc<Card>>) {
+        cards.into_iter().for_each(|card| self.add_card(card));
+    }
+
+    pub fn get_cards(&self) -> &Vec<Arc<Card>> {
+        &self.cards
+    }
+
+    pub fn get_card_count(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn get_rank_count(&self, rank: Rank) -> u32 {
+        self.rank_counts.get(rank)
+    }
+
+    pub fn get_rank_counts(&self) -> &RankCounts {
+        &self.rank_counts
+    }
+
+    pub fn clear(&mut self) {
+        self.cards.clear();
+        self.rank_counts.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::suit::Suit;
+
+    use super::*;
+
+    #[test]
+    fn it_counts_discarded_ranks() {
+        let mut tray = DiscardTray::new();
+
+        tray.add_cards(vec![
+            Arc::new(Card::from(Suit::Club, Rank::Ace)),
+            Arc::new(Card::from(Suit::Heart, Rank::Ace)),
+            Arc::new(Card::from(Suit::Heart, Rank::Nine)),
+        ]);
+
+        assert_eq!(tray.get_card_count(), 3);
+        assert_eq!(tray.get_rank_count(Rank::Ace), 2);
+        assert_eq!(tray.get_rank_count(Rank::Nine), 1);
+        assert_eq!(tray.get_rank_count(Rank::Two), 0);
+
+        tray.clear();
+
+        assert_eq!(tray.get_card_count(), 0);
+        assert_eq!(tray.get_rank_count(Rank::Ace), 0);
+    }
+}
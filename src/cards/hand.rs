@@ -46,6 +46,16 @@ impl Hand {
     pub fn add_cards(&mut self, cards: Vec<Arc<Card>>) {
         self.cards.extend(cards)
     }
+
+    // returns the card that was replaced
+    pub fn replace_card(
+        &mut self,
+        index: usize,
+        card: Arc<Card>,
+    ) -> Option<Arc<Card>> {
+        let slot = self.cards.get_mut(index)?;
+        Some(std::mem::replace(slot, card))
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,123 @@
+use failure::Fail;
+
+use crate::cards::card::Card;
+
+#[derive(Debug, Fail, PartialEq)]
+pub enum ParseCardError {
+    #[fail(display = "Invalid card (card = {:?})", _0)]
+    InvalidCard(String),
+    #[fail(display = "Invalid rank (rank = {:?})", _0)]
+    InvalidRank(String),
+    #[fail(display = "Invalid suit (suit = {:?})", _0)]
+    InvalidSuit(String),
+}
+
+// parses a whitespace separated list of cards, e.g. "A♠ 10h Td K♣"
+pub fn parse_cards(s: &str) -> Result<Vec<Card>, ParseCardError> {
+    s.split_whitespace().map(|card| card.parse::<Card>()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::rank::Rank;
+    use crate::cards::suit::Suit;
+
+    use super::*;
+
+    #[test]
+    fn it_parses_ranks() {
+        assert_eq!("A".parse::<Rank>(), Ok(Rank::Ace));
+        assert_eq!("a".parse::<Rank>(), Ok(Rank::Ace));
+        assert_eq!("7".parse::<Rank>(), Ok(Rank::Seven));
+        assert_eq!("10".parse::<Rank>(), Ok(Rank::Ten));
+        assert_eq!("T".parse::<Rank>(), Ok(Rank::Ten));
+        assert_eq!("q".parse::<Rank>(), Ok(Rank::Queen));
+        assert_eq!(
+            "1".parse::<Rank>(),
+            Err(ParseCardError::InvalidRank(String::from("1")))
+        );
+    }
+
+    #[test]
+    fn it_parses_suits() {
+        assert_eq!("\u{2660}".parse::<Suit>(), Ok(Suit::Spade));
+        assert_eq!("h".parse::<Suit>(), Ok(Suit::Heart));
+        assert_eq!("D".parse::<Suit>(), Ok(Suit::Diamond));
+        assert_eq!("\u{2663}".parse::<Suit>(), Ok(Suit::Club));
+        assert_eq!(
+            "x".parse::<Suit>(),
+            Err(ParseCardError::InvalidSuit(String::from("x")))
+        );
+    }
+
+    #[test]
+    fn it_parses_cards() {
+        assert_eq!(
+            "AS".parse::<Card>(),
+            Ok(Card::from(Suit::Spade, Rank::Ace))
+        );
+        assert_eq!(
+            "10h".parse::<Card>(),
+            Ok(Card::from(Suit::Heart, Rank::Ten))
+        );
+        assert_eq!(
+            "Td".parse::<Card>(),
+            Ok(Card::from(Suit::Diamond, Rank::Ten))
+        );
+        assert_eq!(
+            "K\u{2663}".parse::<Card>(),
+            Ok(Card::from(Suit::Club, Rank::King))
+        );
+    }
+
+    #[test]
+    fn it_parses_display_form_of_card() {
+        Suit::iter().for_each(|suit| {
+            Rank::iter().for_each(|rank| {
+                let card = Card::from(*suit, *rank);
+                assert_eq!(card.to_string().parse::<Card>(), Ok(card));
+            })
+        });
+    }
+
+    #[test]
+    fn it_does_not_parse_invalid_cards() {
+        assert_eq!(
+            "".parse::<Card>(),
+            Err(ParseCardError::InvalidCard(String::from("")))
+        );
+        assert_eq!(
+            "A".parse::<Card>(),
+            Err(ParseCardError::InvalidCard(String::from("A")))
+        );
+        assert_eq!(
+            "AX".parse::<Card>(),
+            Err(ParseCardError::InvalidSuit(String::from("AX")))
+        );
+        assert_eq!(
+            "11S".parse::<Card>(),
+            Err(ParseCardError::InvalidRank(String::from("11")))
+        );
+    }
+
+    #[test]
+    fn it_parses_card_lists() {
+        let cards = parse_cards(" A\u{2660}  10h\tTd\nK\u{2663} ").unwrap();
+
+        assert_eq!(
+            cards,
+            vec![
+                Card::from(Suit::Spade, Rank::Ace),
+                Card::from(Suit::Heart, Rank::Ten),
+                Card::from(Suit::Diamond, Rank::Ten),
+                Card::from(Suit::Club, Rank::King),
+            ]
+        );
+
+        assert_eq!(parse_cards(""), Ok(vec![]));
+        assert_eq!(
+            parse_cards("AS ZZ KC"),
+            Err(ParseCardError::InvalidSuit(String::from("ZZ")))
+        );
+    }
+}
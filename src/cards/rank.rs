@@ -1,7 +1,10 @@
 use std::fmt::{Display, Formatter, Result};
 use std::slice::Iter;
+use std::str::FromStr;
 
-#[derive(Copy, Clone, PartialEq, Hash)]
+use crate::cards::parse::ParseCardError;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 #[allow(dead_code)]
 pub enum Rank {
     Ace,
@@ -38,6 +41,15 @@ impl Rank {
         }
     }
 
+    // position of the rank in `Rank::iter`, used to index per rank counts
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn from_index(index: usize) -> Option<Rank> {
+        Rank::iter().nth(index).cloned()
+    }
+
     #[allow(dead_code)]
     pub fn iter() -> Iter<'static, Rank> {
         static RANKS: [Rank; 13] = [
@@ -64,3 +76,18 @@ impl Display for Rank {
         write!(f, "{}", self.as_str())
     }
 }
+
+// accepts the display form of the rank as well as "T" for ten, ignoring case
+impl FromStr for Rank {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> std::result::Result<Rank, ParseCardError> {
+        match s.to_ascii_uppercase().as_str() {
+            "T" => Ok(Rank::Ten),
+            upper => Rank::iter()
+                .find(|rank| rank.as_str() == upper)
+                .cloned()
+                .ok_or_else(|| ParseCardError::InvalidRank(s.to_string())),
+        }
+    }
+}
@@ -1,28 +1,92 @@
+use std::str::FromStr;
 use std::sync::Arc;
 
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
 use crate::cards::card::Card;
-use crate::cards::deck::Deck;
+use crate::cards::compact::RankCounts;
+use crate::cards::deck::{Deck, CARDS_PER_DECK};
+use crate::cards::deck_spec::DeckSpec;
+use crate::cards::discard_tray::DiscardTray;
+use crate::cards::parse::{parse_cards, ParseCardError};
+use crate::cards::rank::Rank;
+use crate::cards::shuffle::ShuffleProcedure;
 use crate::cards::shuffleable::Shuffleable;
 
+#[derive(PartialEq, Clone, Debug)]
+pub enum ShoeType {
+    // dealt down to the cut card and then reshuffled by hand
+    Standard,
+    // the discards are mixed back into the shoe after every round
+    ContinuousShuffler,
+    // deals the cards in the exact order they were given and never shuffles
+    Stacked,
+}
+
 #[derive(Debug)]
 pub struct Shoe {
     pub cards: Vec<Arc<Card>>,
+    deck_spec: DeckSpec,
+    cards_per_deck: usize,
+    shoe_type: ShoeType,
     curr_index: usize,
     round_index: usize,
+    seed: u64,
+    rng: StdRng,
+    shuffle_procedure: ShuffleProcedure,
+    penetration: f32,
+    cut_card_reached: bool,
+    discard_tray: DiscardTray,
+    remaining_rank_counts: RankCounts,
+    burn_on_shuffle: usize,
+    burn_on_dealer_change: bool,
 }
 
 impl Shuffleable for Shoe {
     fn get_cards(&mut self) -> &mut Vec<Arc<Card>> {
         &mut self.cards
     }
+
+    // the shoe always shuffles with its own rng so that the order of the cards
+    // can be reproduced from the seed, every round runs the whole procedure
+    fn shuffle_rounds(&mut self, rounds: i32) {
+        if self.shoe_type == ShoeType::Stacked {
+            return;
+        }
+
+        for _round in 0..rounds {
+            self.shuffle_procedure.apply(&mut self.cards, &mut self.rng)
+        }
+    }
+
+    // the seed is recorded and the whole shoe is shuffled again with it
+    fn shuffle_with_seed(&mut self, seed: u64) {
+        self.set_seed(seed);
+        self.reshuffle();
+    }
 }
 
 impl Default for Shoe {
     fn default() -> Shoe {
+        let seed = rand::random::<u64>();
+
         Shoe {
             cards: Vec::new(),
+            deck_spec: DeckSpec::standard(),
+            cards_per_deck: CARDS_PER_DECK,
+            shoe_type: ShoeType::Standard,
             curr_index: 0,
             round_index: 0,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            shuffle_procedure: ShuffleProcedure::perfect(),
+            penetration: 0.75,
+            cut_card_reached: false,
+            discard_tray: DiscardTray::new(),
+            remaining_rank_counts: RankCounts::new(),
+            burn_on_shuffle: 0,
+            burn_on_dealer_change: false,
         }
     }
 }
@@ -33,33 +97,183 @@ impl Shoe {
     }
 
     pub fn with_decks(count: u32) -> Shoe {
+        Shoe::with_deck_spec(count, DeckSpec::standard())
+    }
+
+    pub fn with_deck_spec(count: u32, deck_spec: DeckSpec) -> Shoe {
         let mut cards = Vec::new();
 
         for _ in 0..count {
-            let mut _deck = Deck::new();
+            let mut _deck = Deck::from_spec(&deck_spec);
             cards.append(_deck.get_cards());
         }
 
-        Shoe {
+        let mut shoe = Shoe {
             cards,
-            curr_index: 0,
-            round_index: 0,
-        }
+            cards_per_deck: deck_spec.len(),
+            deck_spec,
+            ..Default::default()
+        };
+        shoe.count_remaining_cards();
+        shoe
+    }
+
+    pub fn with_decks_and_seed(count: u32, seed: u64) -> Shoe {
+        let mut shoe = Shoe::with_decks(count);
+        shoe.set_seed(seed);
+        shoe
+    }
+
+    pub fn with_cards(cards: Vec<Arc<Card>>) -> Shoe {
+        let mut shoe = Shoe {
+            cards,
+            shoe_type: ShoeType::Stacked,
+            ..Default::default()
+        };
+        shoe.count_remaining_cards();
+        shoe
+    }
+
+    pub fn continuous_with_decks(count: u32) -> Shoe {
+        let mut shoe = Shoe::with_decks(count);
+        shoe.set_shoe_type(ShoeType::ContinuousShuffler);
+        shoe
+    }
+
+    pub fn get_shoe_type(&self) -> &ShoeType {
+        &self.shoe_type
+    }
+
+    pub fn set_shoe_type(&mut self, shoe_type: ShoeType) {
+        self.shoe_type = shoe_type;
+    }
+
+    pub fn get_shuffle_procedure(&self) -> &ShuffleProcedure {
+        &self.shuffle_procedure
+    }
+
+    pub fn set_shuffle_procedure(&mut self, procedure: ShuffleProcedure) {
+        self.shuffle_procedure = procedure;
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    // resets the rng, the next shuffle will be the first shuffle for the seed
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    pub fn get_deck_spec(&self) -> &DeckSpec {
+        &self.deck_spec
     }
 
     pub fn add_deck(&mut self) {
-        let mut deck = Deck::new();
-        self.cards.append(deck.get_cards())
+        let mut deck = Deck::from_spec(&self.deck_spec);
+        self.cards.append(deck.get_cards());
+        self.count_remaining_cards();
+    }
+
+    pub fn get_penetration(&self) -> f32 {
+        self.penetration
+    }
+
+    // the percent of the shoe that is dealt before the cut card comes out
+    pub fn set_penetration(&mut self, penetration: f32) {
+        self.penetration = penetration.clamp(0.0, 1.0);
+    }
+
+    pub fn get_cut_card_index(&self) -> usize {
+        (self.cards.len() as f32 * self.penetration) as usize
+    }
+
+    pub fn is_cut_card_reached(&self) -> bool {
+        self.cut_card_reached
+    }
+
+    pub fn get_burn_on_shuffle(&self) -> usize {
+        self.burn_on_shuffle
+    }
+
+    // the number of cards burned right after every reshuffle
+    pub fn set_burn_on_shuffle(&mut self, count: usize) {
+        self.burn_on_shuffle = count;
+    }
+
+    pub fn get_burn_on_dealer_change(&self) -> bool {
+        self.burn_on_dealer_change
     }
 
-    pub fn start_round(&mut self) {
+    pub fn set_burn_on_dealer_change(&mut self, burn: bool) {
+        self.burn_on_dealer_change = burn;
+    }
+
+    pub fn reshuffle(&mut self) {
+        self.shuffle();
+        self.curr_index = 0;
+        self.round_index = 0;
+        self.cut_card_reached = false;
+        self.discard_tray.clear();
+        self.count_remaining_cards();
+
+        for _ in 0..self.burn_on_shuffle {
+            self.burn_card();
+        }
+    }
+
+    // burned cards go face down straight into the discard tray and are never
+    // returned from `take_card`. nothing is burned once the round has dealt
+    // cards, they still have to reach the tray when the round ends
+    pub fn burn_card(&mut self) -> bool {
+        if self.curr_index != self.round_index {
+            return false;
+        }
+
+        match self.take_card() {
+            Some(card) => {
+                self.discard_tray.add_card(card);
+                self.round_index = self.curr_index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // returns true when a card was burned for the new dealer
+    pub fn dealer_changed(&mut self) -> bool {
+        self.burn_on_dealer_change && self.burn_card()
+    }
+
+    // returns true when the cut card came out during the last round and the
+    // shoe had to be reshuffled before starting the next one
+    pub fn start_round(&mut self) -> bool {
+        let reshuffled = self.cut_card_reached;
+
+        if reshuffled {
+            self.reshuffle();
+        }
+
         self.round_index = self.curr_index;
+
+        reshuffled
     }
 
     pub fn take_card(&mut self) -> Option<Arc<Card>> {
         match self.cards.get(self.curr_index) {
             Some(card) => {
                 self.curr_index += 1;
+                self.remaining_rank_counts.remove(card.rank);
+
+                // the round in progress is finished before reshuffling, a
+                // continuous shuffler never has to stop for the cut card
+                if self.shoe_type == ShoeType::Standard
+                    && self.curr_index >= self.get_cut_card_index()
+                {
+                    self.cut_card_reached = true;
+                }
+
                 Some(card.clone())
             }
             None => None,
@@ -74,15 +288,98 @@ impl Shoe {
         }
     }
 
+    // moves the cards dealt since the start of the round into the discard tray
+    pub fn end_round(&mut self) {
+        match self.shoe_type {
+            ShoeType::Standard | ShoeType::Stacked => {
+                let cards =
+                    self.cards[self.round_index..self.curr_index].to_vec();
+                self.discard_tray.add_cards(cards);
+                self.round_index = self.curr_index;
+            }
+            ShoeType::ContinuousShuffler => self.return_dealt_cards(),
+        }
+    }
+
+    // every dealt card goes back into the undealt cards at a random position
+    fn return_dealt_cards(&mut self) {
+        let dealt: Vec<Arc<Card>> =
+            self.cards.drain(..self.curr_index).collect();
+
+        dealt.into_iter().for_each(|card| {
+            self.remaining_rank_counts.add(card.rank);
+
+            let position = self.rng.gen_range(0, self.cards.len() + 1);
+            self.cards.insert(position, card);
+        });
+
+        self.curr_index = 0;
+        self.round_index = 0;
+        self.discard_tray.clear();
+    }
+
+    pub fn get_discard_tray(&self) -> &DiscardTray {
+        &self.discard_tray
+    }
+
+    pub fn get_remaining_card_count(&self) -> usize {
+        self.cards.len() - self.curr_index
+    }
+
+    pub fn get_remaining_rank_count(&self, rank: Rank) -> u32 {
+        self.remaining_rank_counts.get(rank)
+    }
+
+    // the composition of the undealt cards, cheap to copy for simulations
+    pub fn get_remaining_rank_counts(&self) -> &RankCounts {
+        &self.remaining_rank_counts
+    }
+
+    pub fn get_discarded_card_count(&self) -> usize {
+        self.discard_tray.get_card_count()
+    }
+
+    pub fn get_discarded_rank_count(&self, rank: Rank) -> u32 {
+        self.discard_tray.get_rank_count(rank)
+    }
+
+    // an empty deck spec has no decks to count
+    pub fn get_decks_remaining(&self) -> f32 {
+        if self.cards_per_deck == 0 {
+            return 0.0;
+        }
+
+        self.get_remaining_card_count() as f32 / self.cards_per_deck as f32
+    }
+
     pub fn get_percent_undealt_cards(&self) -> f32 {
         self.curr_index as f32 / self.cards.len() as f32
     }
+
+    fn count_remaining_cards(&mut self) {
+        self.remaining_rank_counts =
+            RankCounts::from_cards(&self.cards[self.curr_index..]);
+    }
+}
+
+// builds a stacked shoe from a whitespace separated list of cards, the cards
+// are dealt in the order they are listed, e.g. "A♠ K♥ 8♦ 8♣"
+impl FromStr for Shoe {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Shoe, ParseCardError> {
+        let cards = parse_cards(s)?;
+
+        Ok(Shoe::with_cards(cards.into_iter().map(Arc::new).collect()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
 
+    use crate::cards::suit::Suit;
+
     use super::*;
 
     #[test]
@@ -113,6 +410,21 @@ mod tests {
         assert_eq!(shoe.cards.len(), 52 * 2);
     }
 
+    #[test]
+    fn shoe_with_deck_spec() {
+        let mut shoe = Shoe::with_deck_spec(6, DeckSpec::spanish());
+
+        assert_eq!(shoe.cards.len(), 48 * 6);
+        assert_eq!(shoe.get_remaining_rank_count(Rank::Ten), 0);
+        assert_eq!(shoe.get_remaining_rank_count(Rank::Jack), 24);
+        assert_eq!(shoe.get_decks_remaining(), 6.0);
+
+        shoe.add_deck();
+
+        assert_eq!(shoe.cards.len(), 48 * 7);
+        assert_eq!(shoe.get_remaining_rank_count(Rank::Ten), 0);
+    }
+
     #[test]
     fn does_shuffle_cards() {
         let mut shoe = Shoe::with_decks(1);
@@ -127,6 +439,75 @@ mod tests {
         assert_ne!(cards_before_shuffle, cards_after_shuffle);
     }
 
+    #[test]
+    fn same_seed_shuffles_same_order() {
+        let mut shoe = Shoe::with_decks_and_seed(2, 42);
+        let mut other = Shoe::with_decks_and_seed(2, 42);
+
+        shoe.shuffle();
+        other.shuffle();
+
+        assert_eq!(shoe.get_seed(), 42);
+        assert_eq!(shoe.cards, other.cards);
+    }
+
+    #[test]
+    fn different_seeds_shuffle_different_order() {
+        let mut shoe = Shoe::with_decks_and_seed(2, 1);
+        let mut other = Shoe::with_decks_and_seed(2, 2);
+
+        shoe.shuffle();
+        other.shuffle();
+
+        assert_ne!(shoe.cards, other.cards);
+    }
+
+    #[test]
+    fn can_replay_shoe_from_recorded_seed() {
+        let mut shoe = Shoe::with_decks(1);
+        shoe.shuffle();
+
+        let mut replay = Shoe::with_decks_and_seed(1, shoe.get_seed());
+        replay.shuffle();
+
+        assert_eq!(shoe.cards, replay.cards);
+    }
+
+    #[test]
+    fn shuffles_shoe_with_seed() {
+        let mut shoe = Shoe::with_decks(2);
+        let mut other = Shoe::with_decks_and_seed(2, 42);
+
+        shoe.take_card();
+        shoe.shuffle_with_seed(42);
+        other.reshuffle();
+
+        assert_eq!(shoe.get_seed(), 42);
+        assert_eq!(shoe.cards, other.cards);
+        assert_eq!(shoe.get_remaining_card_count(), 104);
+        assert_eq!(shoe.get_remaining_rank_count(Rank::Ace), 8);
+
+        let mut stacked = Shoe::from_str("AS KH 2C").unwrap();
+        let cards = stacked.cards.clone();
+        stacked.shuffle_with_seed(42);
+
+        assert_eq!(stacked.cards, cards);
+    }
+
+    #[test]
+    fn shuffles_with_procedure() {
+        let mut shoe = Shoe::with_decks_and_seed(2, 42);
+        let mut other = Shoe::with_decks_and_seed(2, 42);
+        other.set_shuffle_procedure(ShuffleProcedure::casino());
+
+        shoe.shuffle();
+        other.shuffle();
+
+        assert_eq!(other.get_shuffle_procedure(), &ShuffleProcedure::casino());
+        assert_ne!(shoe.cards, other.cards);
+        assert_eq!(other.get_remaining_rank_count(Rank::Ace), 8);
+    }
+
     #[test]
     fn can_take_card() {
         let mut shoe = Shoe::with_decks(1);
@@ -166,26 +547,292 @@ mod tests {
         assert!(shoe.round_index.eq(&shoe.curr_index));
     }
 
+    #[test]
+    fn should_reach_cut_card_at_penetration() {
+        let mut shoe = Shoe::with_decks(1);
+        shoe.set_penetration(0.5);
+
+        assert_eq!(shoe.get_cut_card_index(), 26);
+
+        for _ in 0..25 {
+            shoe.take_card();
+        }
+        assert!(!shoe.is_cut_card_reached());
+
+        shoe.take_card();
+        assert!(shoe.is_cut_card_reached());
+    }
+
+    #[test]
+    fn should_finish_round_after_cut_card() {
+        let mut shoe = Shoe::with_decks(1);
+        shoe.set_penetration(0.1);
+
+        for _ in 0..10 {
+            shoe.take_card();
+        }
+
+        assert!(shoe.is_cut_card_reached());
+        assert!(shoe.take_card().is_some());
+        assert!(shoe.curr_index.eq(&11));
+    }
+
+    #[test]
+    fn should_reshuffle_before_next_round_after_cut_card() {
+        let mut shoe = Shoe::with_decks(1);
+        shoe.set_penetration(0.1);
+
+        assert!(!shoe.start_round());
+
+        for _ in 0..6 {
+            shoe.take_card();
+        }
+
+        assert!(shoe.start_round());
+        assert!(shoe.curr_index.eq(&0));
+        assert!(shoe.round_index.eq(&0));
+        assert!(!shoe.is_cut_card_reached());
+        assert!(!shoe.start_round());
+    }
+
+    #[test]
+    fn continuous_shuffler_never_reaches_cut_card() {
+        let mut shoe = Shoe::continuous_with_decks(1);
+        shoe.set_penetration(0.1);
+
+        for _ in 0..20 {
+            shoe.take_card();
+        }
+
+        assert!(!shoe.is_cut_card_reached());
+        assert!(!shoe.start_round());
+    }
+
+    #[test]
+    fn continuous_shuffler_returns_discards_after_round() {
+        let mut shoe = Shoe::continuous_with_decks(1);
+        shoe.shuffle();
+
+        shoe.start_round();
+        let dealt: Vec<Arc<Card>> =
+            (0..5).map(|_| shoe.take_card().unwrap()).collect();
+        let undealt = shoe.cards[5..].to_vec();
+
+        assert_eq!(shoe.get_remaining_card_count(), 47);
+
+        shoe.end_round();
+
+        assert_eq!(shoe.get_remaining_card_count(), 52);
+        assert_eq!(shoe.get_discarded_card_count(), 0);
+        assert_eq!(shoe.get_remaining_rank_count(Rank::Ace), 4);
+
+        for card in dealt.iter() {
+            assert!(shoe.cards.contains(card));
+        }
+
+        // the undealt cards keep their order, the discards are mixed in
+        let without_dealt: Vec<Arc<Card>> = shoe
+            .cards
+            .iter()
+            .filter(|card| !dealt.iter().any(|d| Arc::ptr_eq(d, card)))
+            .cloned()
+            .collect();
+        assert_eq!(without_dealt, undealt);
+    }
+
+    #[test]
+    fn should_burn_cards_after_reshuffle() {
+        let mut shoe = Shoe::with_decks(1);
+        shoe.set_burn_on_shuffle(3);
+
+        shoe.reshuffle();
+
+        assert_eq!(shoe.get_remaining_card_count(), 49);
+        assert_eq!(shoe.get_discarded_card_count(), 3);
+
+        let burned = shoe.get_discard_tray().get_cards().clone();
+
+        shoe.start_round();
+        let card = shoe.take_card().unwrap();
+        shoe.end_round();
+
+        assert!(!burned.iter().any(|b| Arc::ptr_eq(b, &card)));
+        assert_eq!(shoe.get_discarded_card_count(), 4);
+    }
+
+    #[test]
+    fn should_burn_card_on_dealer_change() {
+        let mut shoe = Shoe::with_decks(1);
+
+        assert!(!shoe.dealer_changed());
+        assert_eq!(shoe.get_discarded_card_count(), 0);
+
+        shoe.set_burn_on_dealer_change(true);
+
+        assert!(shoe.dealer_changed());
+        assert_eq!(shoe.get_discarded_card_count(), 1);
+        assert_eq!(shoe.get_remaining_card_count(), 51);
+
+        shoe.start_round();
+        shoe.end_round();
+
+        assert_eq!(shoe.get_discarded_card_count(), 1);
+    }
+
+    #[test]
+    fn should_not_burn_card_during_round() {
+        let mut shoe = Shoe::from_str("AS KH 8D 10C").unwrap();
+        shoe.set_burn_on_dealer_change(true);
+
+        shoe.start_round();
+        shoe.take_card();
+        shoe.take_card();
+
+        assert!(!shoe.dealer_changed());
+        assert_eq!(shoe.get_remaining_card_count(), 2);
+
+        shoe.end_round();
+
+        assert_eq!(shoe.get_discarded_card_count(), 2);
+        assert!(shoe.dealer_changed());
+        assert_eq!(shoe.get_discarded_card_count(), 3);
+    }
+
+    #[test]
+    fn stacked_shoe_deals_cards_in_order() {
+        let mut shoe =
+            Shoe::from_str("A\u{2660} K\u{2665} 8\u{2666} 10\u{2663}").unwrap();
+        shoe.reshuffle();
+
+        assert_eq!(shoe.get_shoe_type(), &ShoeType::Stacked);
+        assert_eq!(
+            shoe.take_card().unwrap().as_ref(),
+            &Card::from(Suit::Spade, Rank::Ace)
+        );
+        assert_eq!(
+            shoe.take_card().unwrap().as_ref(),
+            &Card::from(Suit::Heart, Rank::King)
+        );
+        assert_eq!(
+            shoe.take_card().unwrap().as_ref(),
+            &Card::from(Suit::Diamond, Rank::Eight)
+        );
+        assert_eq!(
+            shoe.take_card().unwrap().as_ref(),
+            &Card::from(Suit::Club, Rank::Ten)
+        );
+        assert!(shoe.take_card().is_none());
+        assert!(!shoe.is_cut_card_reached());
+    }
+
+    #[test]
+    fn stacked_shoe_does_not_parse_invalid_cards() {
+        assert!(Shoe::from_str("A\u{2660} Z\u{2665}").is_err());
+        assert!(Shoe::from_str("A").is_err());
+    }
+
+    #[test]
+    fn should_clamp_penetration() {
+        let mut shoe = Shoe::with_decks(1);
+
+        shoe.set_penetration(1.5);
+        assert_eq!(shoe.get_penetration(), 1.0);
+
+        shoe.set_penetration(-1.0);
+        assert_eq!(shoe.get_penetration(), 0.0);
+    }
+
     #[test]
     fn should_get_cards_in_play() {
-        //        let mut shoe = Shoe::with_decks(1);
-        //
-        //        shoe.start_round();
-        //
-        //        let mut taken_cards: HashSet<Rc<Card>> = HashSet::new();
-        //        let card1 = shoe.take_card().unwrap();
-        //        let card2 = shoe.take_card().unwrap();
-        //        let card3 = shoe.take_card().unwrap();
-        //        taken_cards.insert(card1);
-        //        taken_cards.insert(card2);
-        //        taken_cards.insert(card3);
-        //
-        //        let in_play_cards = shoe.get_cards_in_play().unwrap();
-        //
-        //        assert!(taken_cards.len().eq(&in_play_cards.iter().len()));
-        //
-        //        for card in in_play_cards {
-        //            assert!(taken_cards.contains(card));
-        //        }
+        let mut shoe = Shoe::with_decks(1);
+
+        shoe.start_round();
+
+        let mut taken_cards: HashSet<Arc<Card>> = HashSet::new();
+        let card1 = shoe.take_card().unwrap();
+        let card2 = shoe.take_card().unwrap();
+        let card3 = shoe.take_card().unwrap();
+        taken_cards.insert(card1);
+        taken_cards.insert(card2);
+        taken_cards.insert(card3);
+
+        let in_play_cards = shoe.get_cards_in_play().unwrap();
+
+        assert!(taken_cards.len().eq(&in_play_cards.iter().len()));
+
+        for card in in_play_cards {
+            assert!(taken_cards.contains(card));
+        }
+    }
+
+    #[test]
+    fn should_discard_cards_in_play_at_end_of_round() {
+        let mut shoe = Shoe::with_decks(1);
+
+        shoe.start_round();
+        let card = shoe.take_card().unwrap();
+        shoe.take_card();
+        shoe.end_round();
+
+        assert_eq!(shoe.get_discarded_card_count(), 2);
+        assert!(shoe.get_discard_tray().get_cards().contains(&card));
+        assert_eq!(shoe.get_cards_in_play().unwrap().len(), 0);
+
+        shoe.start_round();
+        shoe.take_card();
+        shoe.end_round();
+
+        assert_eq!(shoe.get_discarded_card_count(), 3);
+    }
+
+    #[test]
+    fn should_track_remaining_and_discarded_ranks() {
+        let mut shoe = Shoe::with_decks(2);
+
+        assert_eq!(shoe.get_remaining_card_count(), 104);
+        assert_eq!(shoe.get_decks_remaining(), 2.0);
+
+        Rank::iter().for_each(|rank| {
+            assert_eq!(shoe.get_remaining_rank_count(*rank), 8);
+        });
+
+        shoe.start_round();
+        let card = shoe.take_card().unwrap();
+
+        assert_eq!(shoe.get_remaining_card_count(), 103);
+        assert_eq!(shoe.get_remaining_rank_count(card.rank), 7);
+        assert_eq!(shoe.get_discarded_rank_count(card.rank), 0);
+
+        shoe.end_round();
+
+        assert_eq!(shoe.get_discarded_rank_count(card.rank), 1);
+        assert_eq!(shoe.get_remaining_rank_count(card.rank), 7);
+    }
+
+    #[test]
+    fn should_empty_discard_tray_on_reshuffle() {
+        let mut shoe = Shoe::with_decks(1);
+
+        for _ in 0..26 {
+            shoe.take_card();
+        }
+        shoe.end_round();
+
+        assert_eq!(shoe.get_discarded_card_count(), 26);
+        assert_eq!(shoe.get_decks_remaining(), 0.5);
+
+        shoe.reshuffle();
+
+        assert_eq!(shoe.get_discarded_card_count(), 0);
+        assert_eq!(shoe.get_remaining_card_count(), 52);
+        assert_eq!(shoe.get_remaining_rank_count(Rank::Ace), 4);
+    }
+
+    #[test]
+    fn it_counts_no_decks_for_empty_spec() {
+        let shoe = Shoe::with_deck_spec(6, DeckSpec::custom(vec![]));
+
+        assert_eq!(shoe.get_remaining_card_count(), 0);
+        assert_eq!(shoe.get_decks_remaining(), 0.0);
     }
 }
@@ -0,0 +1,291 @@
+use rand::distributions::{Binomial, Distribution};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+// a single step of a dealer's shuffle
+#[derive(Clone, PartialEq, Debug)]
+pub enum ShuffleStep {
+    // Gilbert-Shannon-Reeds riffle, the cut is binomial and cards drop from
+    // each half with probability proportional to the size of the half
+    Riffle,
+    // strips packets of about the given size off the top onto a new pile,
+    // reversing the order of the packets
+    Strip(usize),
+    // cuts the cards near the middle and puts the top half on the bottom
+    Cut,
+    // takes a clump of about the given size off the bottom and plugs it back
+    // in at a random position
+    Plug(usize),
+    // a complete randomization, the same as the Fisher-Yates shuffle
+    Wash,
+}
+
+impl ShuffleStep {
+    pub fn apply<T, R: Rng + ?Sized>(&self, cards: &mut Vec<T>, rng: &mut R) {
+        if cards.len() < 2 {
+            return;
+        }
+
+        match *self {
+            ShuffleStep::Riffle => riffle(cards, rng),
+            ShuffleStep::Strip(size) => strip(cards, size, rng),
+            ShuffleStep::Cut => {
+                let at = binomial_cut(cards.len(), rng);
+                cards.rotate_left(at);
+            }
+            ShuffleStep::Plug(size) => plug(cards, size, rng),
+            ShuffleStep::Wash => cards.shuffle(rng),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ShuffleProcedure {
+    steps: Vec<ShuffleStep>,
+}
+
+impl Default for ShuffleProcedure {
+    fn default() -> ShuffleProcedure {
+        ShuffleProcedure::perfect()
+    }
+}
+
+impl ShuffleProcedure {
+    pub fn new(steps: Vec<ShuffleStep>) -> ShuffleProcedure {
+        ShuffleProcedure {
+            steps,
+        }
+    }
+
+    pub fn perfect() -> ShuffleProcedure {
+        ShuffleProcedure::new(vec![ShuffleStep::Wash])
+    }
+
+    // riffle, strip, riffle, riffle and cut, a common casino shuffle
+    pub fn casino() -> ShuffleProcedure {
+        ShuffleProcedure::new(vec![
+            ShuffleStep::Riffle,
+            ShuffleStep::Strip(8),
+            ShuffleStep::Riffle,
+            ShuffleStep::Riffle,
+            ShuffleStep::Cut,
+        ])
+    }
+
+    pub fn get_steps(&self) -> &Vec<ShuffleStep> {
+        &self.steps
+    }
+
+    pub fn apply<T, R: Rng + ?Sized>(&self, cards: &mut Vec<T>, rng: &mut R) {
+        self.steps.iter().for_each(|step| step.apply(cards, rng));
+    }
+
+    // where each card ends up, `permutation[new_position] = old_position`
+    pub fn permutation<R: Rng + ?Sized>(
+        &self,
+        len: usize,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        let mut positions: Vec<usize> = (0..len).collect();
+        self.apply(&mut positions, rng);
+        positions
+    }
+}
+
+// the number of rising sequences in the permutation, an ordered deck has one
+// and a single riffle of an ordered deck has at most two
+pub fn rising_sequences(permutation: &[usize]) -> usize {
+    if permutation.is_empty() {
+        return 0;
+    }
+
+    let mut new_positions = vec![0; permutation.len()];
+    permutation
+        .iter()
+        .enumerate()
+        .for_each(|(new, &old)| new_positions[old] = new);
+
+    1 + new_positions.windows(2).filter(|pair| pair[1] < pair[0]).count()
+}
+
+// the correlation between the positions of the cards before and after the
+// shuffle, 1.0 when nothing moved and close to 0.0 after a random shuffle
+pub fn positional_correlation(permutation: &[usize]) -> f64 {
+    let n = permutation.len() as f64;
+
+    if permutation.len() < 2 {
+        return 1.0;
+    }
+
+    // both positions are a permutation of 0..n, so they share a mean and
+    // variance
+    let mean = (n - 1.0) / 2.0;
+    let variance: f64 =
+        (0..permutation.len()).map(|p| (p as f64 - mean).powi(2)).sum();
+    let covariance: f64 = permutation
+        .iter()
+        .enumerate()
+        .map(|(new, &old)| (new as f64 - mean) * (old as f64 - mean))
+        .sum();
+
+    covariance / variance
+}
+
+fn binomial_cut<R: Rng + ?Sized>(len: usize, rng: &mut R) -> usize {
+    Binomial::new(len as u64, 0.5).sample(rng) as usize
+}
+
+fn riffle<T, R: Rng + ?Sized>(cards: &mut Vec<T>, rng: &mut R) {
+    let at = binomial_cut(cards.len(), rng);
+
+    let mut right = cards.split_off(at);
+    let mut left = std::mem::replace(cards, Vec::with_capacity(at));
+
+    // cards are dropped from the bottom of each half, so build the result in
+    // reverse
+    let mut riffled = Vec::with_capacity(left.len() + right.len());
+
+    while !left.is_empty() || !right.is_empty() {
+        let (l, r) = (left.len(), right.len());
+
+        let card = if rng.gen_range(0, l + r) < l {
+            left.pop()
+        } else {
+            right.pop()
+        };
+
+        riffled.extend(card);
+    }
+
+    riffled.reverse();
+    *cards = riffled;
+}
+
+fn strip<T, R: Rng + ?Sized>(cards: &mut Vec<T>, size: usize, rng: &mut R) {
+    let size = size.max(1);
+    let mut stripped = Vec::with_capacity(cards.len());
+
+    while !cards.is_empty() {
+        // packets vary by up to half of the target size in either direction
+        let spread = size / 2;
+        let packet =
+            rng.gen_range(size - spread, size + spread + 1).min(cards.len());
+
+        let rest = cards.split_off(packet);
+        let packet = std::mem::replace(cards, rest);
+
+        stripped.splice(0..0, packet);
+    }
+
+    *cards = stripped;
+}
+
+fn plug<T, R: Rng + ?Sized>(cards: &mut Vec<T>, size: usize, rng: &mut R) {
+    let spread = size / 2;
+    let size = rng.gen_range(size - spread, size + spread + 1).min(cards.len());
+
+    let clump = cards.split_off(cards.len() - size);
+    let position = rng.gen_range(0, cards.len() + 1);
+
+    cards.splice(position..position, clump);
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    fn sorted(mut positions: Vec<usize>) -> Vec<usize> {
+        positions.sort();
+        positions
+    }
+
+    #[test]
+    fn every_step_keeps_every_card() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        vec![
+            ShuffleStep::Riffle,
+            ShuffleStep::Strip(6),
+            ShuffleStep::Cut,
+            ShuffleStep::Plug(10),
+            ShuffleStep::Wash,
+        ]
+        .into_iter()
+        .for_each(|step| {
+            let procedure = ShuffleProcedure::new(vec![step]);
+            let permutation = procedure.permutation(52, &mut rng);
+
+            assert_eq!(sorted(permutation), (0..52).collect::<Vec<usize>>());
+        });
+    }
+
+    #[test]
+    fn single_riffle_has_at_most_two_rising_sequences() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let procedure = ShuffleProcedure::new(vec![ShuffleStep::Riffle]);
+
+        for _ in 0..20 {
+            let permutation = procedure.permutation(52, &mut rng);
+            assert!(rising_sequences(&permutation) <= 2);
+        }
+    }
+
+    #[test]
+    fn cut_keeps_one_or_two_rising_sequences() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let procedure = ShuffleProcedure::new(vec![ShuffleStep::Cut]);
+        let permutation = procedure.permutation(52, &mut rng);
+
+        assert!(rising_sequences(&permutation) <= 2);
+        assert_eq!(permutation[1], (permutation[0] + 1) % 52);
+    }
+
+    #[test]
+    fn strip_reverses_packets() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let procedure = ShuffleProcedure::new(vec![ShuffleStep::Strip(1)]);
+
+        // packets of a single card reverse the deck
+        let permutation = procedure.permutation(10, &mut rng);
+        assert_eq!(permutation, (0..10).rev().collect::<Vec<usize>>());
+        assert_eq!(positional_correlation(&permutation), -1.0);
+    }
+
+    #[test]
+    fn measures_ordered_cards() {
+        let permutation: Vec<usize> = (0..52).collect();
+
+        assert_eq!(rising_sequences(&permutation), 1);
+        assert_eq!(positional_correlation(&permutation), 1.0);
+        assert_eq!(rising_sequences(&[]), 0);
+    }
+
+    #[test]
+    fn wash_is_more_random_than_single_riffle() {
+        let mut rng = StdRng::seed_from_u64(11);
+
+        let riffle = ShuffleProcedure::new(vec![ShuffleStep::Riffle]);
+        let riffled = riffle.permutation(312, &mut rng);
+
+        let washed = ShuffleProcedure::perfect().permutation(312, &mut rng);
+
+        assert!(rising_sequences(&washed) > rising_sequences(&riffled));
+        assert!(positional_correlation(&riffled) > 0.3);
+        assert!(positional_correlation(&washed).abs() < 0.2);
+    }
+
+    #[test]
+    fn casino_shuffle_is_reproducible() {
+        let procedure = ShuffleProcedure::casino();
+
+        let permutation =
+            procedure.permutation(52, &mut StdRng::seed_from_u64(1));
+        let replay = procedure.permutation(52, &mut StdRng::seed_from_u64(1));
+
+        assert_eq!(permutation, replay);
+        assert!(rising_sequences(&permutation) > 1);
+    }
+}
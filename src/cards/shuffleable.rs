@@ -1,6 +1,9 @@
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
 
 use crate::cards::card::Card;
+use crate::cards::shuffle::ShuffleProcedure;
 use std::sync::Arc;
 
 pub trait Shuffleable {
@@ -12,8 +15,30 @@ pub trait Shuffleable {
 
     fn shuffle_rounds(&mut self, rounds: i32) {
         let mut rng = rand::thread_rng();
+        self.shuffle_rounds_with_rng(rounds, &mut rng);
+    }
+
+    // the same seed will always produce the same order of cards
+    fn shuffle_with_seed(&mut self, seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        self.shuffle_rounds_with_rng(2, &mut rng);
+    }
+
+    fn shuffle_rounds_with_rng<R: Rng + ?Sized>(
+        &mut self,
+        rounds: i32,
+        rng: &mut R,
+    ) {
         for _round in 0..rounds {
-            self.get_cards().shuffle(&mut rng)
+            self.get_cards().shuffle(rng)
         }
     }
+
+    fn shuffle_with_procedure<R: Rng + ?Sized>(
+        &mut self,
+        procedure: &ShuffleProcedure,
+        rng: &mut R,
+    ) {
+        procedure.apply(self.get_cards(), rng);
+    }
 }
@@ -1,7 +1,10 @@
 use std::fmt::{Display, Formatter, Result};
 use std::slice::Iter;
+use std::str::FromStr;
 
-#[derive(Copy, Clone, PartialEq, Hash)]
+use crate::cards::parse::ParseCardError;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 #[allow(dead_code)]
 pub enum Suit {
     Spade,
@@ -20,6 +23,19 @@ impl Suit {
         }
     }
 
+    // position of the suit in `Suit::iter`
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn from_index(index: usize) -> Option<Suit> {
+        Suit::iter().nth(index).cloned()
+    }
+
+    pub fn is_red(&self) -> bool {
+        *self == Suit::Heart || *self == Suit::Diamond
+    }
+
     pub fn iter() -> Iter<'static, Suit> {
         static SUITS: [Suit; 4] =
             [Suit::Spade, Suit::Heart, Suit::Diamond, Suit::Club];
@@ -32,3 +48,18 @@ impl Display for Suit {
         write!(f, "{}", self.as_str())
     }
 }
+
+// accepts the suit glyphs as well as the first letter of the suit, ignoring case
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> std::result::Result<Suit, ParseCardError> {
+        match s.to_ascii_uppercase().as_str() {
+            "\u{2660}" | "S" => Ok(Suit::Spade),
+            "\u{2665}" | "H" => Ok(Suit::Heart),
+            "\u{2666}" | "D" => Ok(Suit::Diamond),
+            "\u{2663}" | "C" => Ok(Suit::Club),
+            _ => Err(ParseCardError::InvalidSuit(s.to_string())),
+        }
+    }
+}